@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+
+const FLIP: Duration = Duration::from_millis(250);
+const POP: Duration = Duration::from_millis(120);
+const BOUNCE_STEP: Duration = Duration::from_millis(80);
+const BOUNCE: Duration = Duration::from_millis(240);
+const SHAKE: Duration = Duration::from_millis(400);
+const SHAKE_OFFSETS: [i32; 7] = [-2, 2, -2, 2, -1, 1, 0];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Effect {
+    Reveal,
+    Pop(usize),
+    Bounce,
+    Shake,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Animation {
+    pub(crate) row: usize,
+    pub(crate) effect: Effect,
+    start: Instant,
+}
+
+impl Animation {
+    pub(crate) fn new(row: usize, effect: Effect) -> Self {
+        Self {
+            row,
+            effect,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn after(mut self, delay: Duration) -> Self {
+        self.start += delay;
+        self
+    }
+
+    pub(crate) fn duration(&self) -> Duration {
+        match self.effect {
+            Effect::Reveal => FLIP * 5,
+            Effect::Pop(_) => POP,
+            Effect::Bounce => BOUNCE_STEP * 4 + BOUNCE,
+            Effect::Shake => SHAKE,
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        Instant::now() >= self.start + self.duration()
+    }
+
+    /// Progress of the tile in `col` through its part of the effect, if it's currently moving.
+    fn progress(&self, col: usize) -> Option<f32> {
+        let elapsed = Instant::now().checked_duration_since(self.start)?;
+        let (start, length) = match self.effect {
+            Effect::Reveal => (FLIP * col as u32, FLIP),
+            Effect::Pop(c) if c == col => (Duration::ZERO, POP),
+            Effect::Pop(_) => return None,
+            Effect::Bounce => (BOUNCE_STEP * col as u32, BOUNCE),
+            Effect::Shake => (Duration::ZERO, SHAKE),
+        };
+        let elapsed = elapsed.checked_sub(start)?;
+        (elapsed < length).then(|| elapsed.as_secs_f32() / length.as_secs_f32())
+    }
+
+    /// Whether the tile in `col` should still be drawn without its color.
+    pub(crate) fn hides(&self, col: usize) -> bool {
+        if self.effect != Effect::Reveal {
+            return false;
+        }
+        match Instant::now().checked_duration_since(self.start) {
+            Some(elapsed) => elapsed < FLIP * col as u32 + FLIP / 2,
+            None => true,
+        }
+    }
+
+    pub(crate) fn transform(&self, col: usize, area: Rect) -> Rect {
+        let Some(progress) = self.progress(col) else {
+            return area;
+        };
+
+        match self.effect {
+            Effect::Reveal => {
+                let height = (area.height as f32 * (1.0 - 2.0 * progress).abs()).round() as u16;
+                Rect {
+                    y: area.y + (area.height - height) / 2,
+                    height,
+                    ..area
+                }
+            }
+            Effect::Pop(_) => Rect {
+                x: area.x.saturating_sub(1),
+                width: area.width + 2,
+                ..area
+            },
+            Effect::Bounce if (0.25..0.75).contains(&progress) => Rect {
+                y: area.y.saturating_sub(1),
+                ..area
+            },
+            Effect::Bounce => area,
+            Effect::Shake => {
+                let offset = SHAKE_OFFSETS[(progress * SHAKE_OFFSETS.len() as f32) as usize];
+                Rect {
+                    x: area.x.saturating_add_signed(offset as i16),
+                    ..area
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::{Duration, NaiveDate};
+use ratatui::widgets::TableState;
+
+use crate::{
+    SaveData,
+    manager::{FIRST_WORDLE_DATE, date_to_wordle_number},
+    save::GameId,
+    wordle,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum Filter {
+    #[default]
+    All,
+    Unplayed,
+    InProgress,
+    Won,
+    Lost,
+}
+
+impl Filter {
+    pub(crate) const ALL: [Filter; 5] = [
+        Filter::All,
+        Filter::Unplayed,
+        Filter::InProgress,
+        Filter::Won,
+        Filter::Lost,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Filter::All => "All",
+            Filter::Unplayed => "Unplayed",
+            Filter::InProgress => "In progress",
+            Filter::Won => "Won",
+            Filter::Lost => "Lost",
+        }
+    }
+
+    fn matches(self, status: Status) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Unplayed => status == Status::Unplayed,
+            Filter::InProgress => status == Status::InProgress,
+            Filter::Won => matches!(status, Status::Won(_)),
+            Filter::Lost => status == Status::Lost,
+        }
+    }
+
+    fn position(self) -> usize {
+        Self::ALL.iter().position(|f| *f == self).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Status {
+    Unplayed,
+    InProgress,
+    Won(usize),
+    Lost,
+}
+
+impl From<&wordle::Game> for Status {
+    fn from(game: &wordle::Game) -> Self {
+        if let Some(guesses) = game.won_in() {
+            Status::Won(guesses)
+        } else if game.has_finished() {
+            Status::Lost
+        } else if game.has_started() {
+            Status::InProgress
+        } else {
+            Status::Unplayed
+        }
+    }
+}
+
+pub(crate) struct Entry {
+    pub(crate) number: u32,
+    pub(crate) date: NaiveDate,
+    pub(crate) status: Status,
+    pub(crate) guesses: usize,
+    pub(crate) answer: Option<String>,
+}
+
+pub(crate) struct Archive {
+    entries: Vec<Entry>,
+    visible: Vec<usize>,
+    pub(crate) filter: Filter,
+    pub(crate) state: TableState,
+}
+
+impl Archive {
+    pub(crate) fn new(save_data: &SaveData, last: NaiveDate) -> Self {
+        let mut entries = Vec::new();
+
+        let mut date = last;
+        while date >= FIRST_WORDLE_DATE {
+            let number = date_to_wordle_number(date);
+            let game = save_data.load(&GameId::Daily(number));
+
+            entries.push(Entry {
+                number,
+                date,
+                status: game.map_or(Status::Unplayed, Status::from),
+                guesses: game.map_or(0, |game| game.index.0),
+                answer: game
+                    .and_then(|game| game.answer())
+                    .map(|answer| answer.to_uppercase()),
+            });
+
+            date -= Duration::days(1);
+        }
+
+        let mut archive = Self {
+            entries,
+            visible: Vec::new(),
+            filter: Filter::default(),
+            state: TableState::new(),
+        };
+        archive.apply_filter();
+        archive
+    }
+
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &Entry> {
+        self.visible.iter().map(|i| &self.entries[*i])
+    }
+
+    pub(crate) fn select_date(&mut self, date: NaiveDate) {
+        let position = self.rows().position(|entry| entry.date == date);
+        if position.is_some() {
+            self.state.select(position);
+        }
+    }
+
+    pub(crate) fn selected(&self) -> Option<NaiveDate> {
+        self.state
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .map(|i| self.entries[*i].date)
+    }
+
+    pub(crate) fn up(&mut self) {
+        self.state.select_previous();
+    }
+
+    pub(crate) fn down(&mut self) {
+        if self.state.selected() < Some(self.visible.len().saturating_sub(1)) {
+            self.state.select_next();
+        }
+    }
+
+    pub(crate) fn top(&mut self) {
+        self.state.select_first();
+    }
+
+    pub(crate) fn bottom(&mut self) {
+        if !self.visible.is_empty() {
+            self.state.select(Some(self.visible.len() - 1));
+        }
+    }
+
+    pub(crate) fn next_filter(&mut self) {
+        self.filter = Filter::ALL[(self.filter.position() + 1) % Filter::ALL.len()];
+        self.apply_filter();
+    }
+
+    pub(crate) fn previous_filter(&mut self) {
+        self.filter =
+            Filter::ALL[(self.filter.position() + Filter::ALL.len() - 1) % Filter::ALL.len()];
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        let selected = self.selected();
+
+        self.visible = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.filter.matches(entry.status))
+            .map(|(i, _)| i)
+            .collect();
+
+        *self.state.offset_mut() = 0;
+        self.state.select((!self.visible.is_empty()).then_some(0));
+        if let Some(date) = selected {
+            self.select_date(date);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Instant;
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+
+use crate::{solver::Solver, wordle};
+
+const BAR_WIDTH: usize = 40;
+
+pub(crate) fn run(solver: Solver, opener: Option<String>) -> anyhow::Result<()> {
+    let answers = wordle::answers().collect::<Vec<_>>();
+    let opener = match opener {
+        Some(opener) => {
+            let opener = opener.to_lowercase();
+            anyhow::ensure!(
+                wordle::is_allowed(&opener),
+                "{opener} is not in the word list"
+            );
+            opener
+        }
+        None => solver.guess(&answers, &answers).to_string(),
+    };
+
+    let start = Instant::now();
+    let results = answers
+        .par_iter()
+        .map(|answer| solver.solve(answer, &opener, &answers))
+        .collect::<Vec<_>>();
+    let elapsed = start.elapsed();
+
+    let wins = results.iter().flatten().copied().collect::<Vec<_>>();
+    let failures = results.len() - wins.len();
+    let mut histogram = [0; 7];
+    for result in &results {
+        histogram[result.map_or(6, |n| n - 1)] += 1;
+    }
+
+    let name = solver.to_possible_value().expect("solvers aren't hidden");
+    println!("Solver:   {}", name.get_name());
+    println!("Opener:   {}", opener.to_uppercase());
+    println!("Answers:  {}", answers.len());
+    println!(
+        "Average:  {:.3}",
+        wins.iter().sum::<usize>() as f64 / wins.len().max(1) as f64
+    );
+    println!("Worst:    {}", wins.iter().max().unwrap_or(&0));
+    println!("Failures: {failures}");
+    println!("Time:     {elapsed:.2?}");
+    println!();
+
+    let max = histogram.iter().max().copied().unwrap_or(1).max(1);
+    let width = max.to_string().len();
+    for (i, count) in histogram.iter().enumerate() {
+        let label = if i < 6 {
+            (i + 1).to_string()
+        } else {
+            "X".to_string()
+        };
+        let bar = "#".repeat(count * BAR_WIDTH / max);
+        println!("{label} {count:>width$} {bar}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::io::{self, BufRead, Write};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::config,
+    manager,
+    wordle::{self, Game, GameInfo},
+};
+
+#[derive(Deserialize)]
+struct Request {
+    guess: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    result: Option<[wordle::Color; 5]>,
+    valid: bool,
+    finished: bool,
+    guesses_left: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+}
+
+impl Response {
+    fn new(game: &Game, result: Result<[wordle::Color; 5], String>) -> Self {
+        let finished = game.has_finished();
+        let (result, error) = match result {
+            Ok(colors) => (Some(colors), None),
+            Err(e) => (None, Some(e)),
+        };
+
+        Self {
+            valid: result.is_some(),
+            result,
+            finished,
+            guesses_left: 6 - game.index.0,
+            error,
+            answer: game.answer(),
+        }
+    }
+}
+
+fn play(game: &mut Game, line: &str) -> Result<[wordle::Color; 5], String> {
+    let request: Request =
+        serde_json::from_str(line).map_err(|e| format!("Invalid request: {e}"))?;
+    if game.has_finished() {
+        return Err("Game is over".to_string());
+    }
+
+    let row = game.index.0;
+    game.guess(request.guess.trim())
+        .map_err(|e| e.to_string())?;
+    Ok(game.grid[row].letters.map(|l| l.color.unwrap_or_default()))
+}
+
+pub(crate) async fn run(
+    date: Option<NaiveDate>,
+    seed: Option<u64>,
+    challenge: Option<&str>,
+) -> anyhow::Result<()> {
+    let info = match (seed, challenge, date) {
+        (Some(seed), ..) => GameInfo::seeded(seed),
+        (None, Some(code), _) => {
+            GameInfo::challenge(code).with_context(|| format!("Failed to open challenge {code}"))?
+        }
+        (None, None, date) => {
+            let date = date.unwrap_or_else(manager::today);
+            GameInfo::at(&config().source, date)
+                .await
+                .with_context(|| format!("Failed to fetch the Wordle for {date}"))?
+        }
+    };
+    let mut game = Game::from(info);
+
+    let mut out = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = play(&mut game, &line);
+        serde_json::to_writer(&mut out, &Response::new(&game, result))?;
+        writeln!(out)?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
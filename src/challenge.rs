@@ -0,0 +1,208 @@
+use std::{fmt, sync::LazyLock};
+
+use crate::wordle::{self, Answer, GameInfo};
+
+/// Crockford's base32, which leaves out letters that are easily mistaken for digits.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const LENGTH: usize = 5;
+const BITS: u32 = 5 * LENGTH as u32;
+const MASK: u32 = (1 << BITS) - 1;
+const CHECKSUM_BITS: u32 = 9;
+/// Scrambles codes so neighbouring words don't get similar ones. Must be odd to be reversible.
+const MULTIPLIER: u32 = 0x1A7_4E35;
+
+static ANSWERS: LazyLock<Vec<&str>> = LazyLock::new(|| wordle::answers().collect());
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ChallengeError {
+    NotAnAnswer,
+    Length,
+    Character(char),
+    Checksum,
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChallengeError::NotAnAnswer => {
+                write!(f, "Challenges can only use words from the answer list")
+            }
+            ChallengeError::Length => write!(f, "Challenge codes are {LENGTH} characters long"),
+            ChallengeError::Character(c) => write!(f, "{c} can't be in a challenge code"),
+            ChallengeError::Checksum => write!(f, "That challenge code has a typo in it"),
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+fn checksum(index: u32) -> u32 {
+    (index.wrapping_mul(0x9E37_79B9) >> 7) & ((1 << CHECKSUM_BITS) - 1)
+}
+
+/// The inverse of `MULTIPLIER` modulo 2^BITS, by Newton's method.
+fn inverse() -> u32 {
+    let mut inverse = MULTIPLIER;
+    for _ in 0..5 {
+        inverse = inverse.wrapping_mul(2u32.wrapping_sub(MULTIPLIER.wrapping_mul(inverse)));
+    }
+    inverse & MASK
+}
+
+/// The code for a challenge to guess `word`.
+pub(crate) fn encode(word: &str) -> Result<String, ChallengeError> {
+    let word = word.to_lowercase();
+    let index = ANSWERS
+        .iter()
+        .position(|&answer| answer == word)
+        .ok_or(ChallengeError::NotAnAnswer)? as u32;
+
+    let value = ((index << CHECKSUM_BITS) | checksum(index)).wrapping_mul(MULTIPLIER) & MASK;
+    Ok((0..LENGTH)
+        .rev()
+        .map(|i| ALPHABET[(value >> (5 * i) & 31) as usize] as char)
+        .collect())
+}
+
+/// The word a challenge code is for. Case, dashes and spaces are ignored, and letters that look
+/// like digits are read as them.
+pub(crate) fn decode(code: &str) -> Result<&'static str, ChallengeError> {
+    let mut value = 0;
+    let mut length = 0;
+    for c in code.chars().filter(|c| !matches!(c, '-' | ' ')) {
+        let digit = match c.to_ascii_uppercase() {
+            'O' => b'0',
+            'I' | 'L' => b'1',
+            c => c as u8,
+        };
+        let digit = ALPHABET
+            .iter()
+            .position(|&d| d == digit && c.is_ascii())
+            .ok_or(ChallengeError::Character(c))?;
+        value = value << 5 | digit as u32;
+        length += 1;
+    }
+    if length != LENGTH {
+        return Err(ChallengeError::Length);
+    }
+
+    let value = value.wrapping_mul(inverse()) & MASK;
+    let index = value >> CHECKSUM_BITS;
+    if checksum(index) != value & ((1 << CHECKSUM_BITS) - 1) {
+        return Err(ChallengeError::Checksum);
+    }
+    ANSWERS
+        .get(index as usize)
+        .copied()
+        .ok_or(ChallengeError::Checksum)
+}
+
+/// Formats `code` the way it's shown, so the same challenge is always saved under one code.
+pub(crate) fn normalize(code: &str) -> Result<String, ChallengeError> {
+    encode(decode(code)?)
+}
+
+impl GameInfo {
+    pub(crate) fn challenge(code: &str) -> Result<Self, ChallengeError> {
+        let word = decode(code)?;
+        Ok(Self {
+            id: 0,
+            number: 0,
+            word: Answer::new(word),
+            date_string: format!("challenge {}", encode(word)?),
+            editor: None,
+        })
+    }
+}
+
+pub(crate) fn run(word: &str) -> anyhow::Result<()> {
+    let code = encode(word)?;
+    println!("Challenge code: {code}");
+    println!("Play it with: wordle --challenge {code}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        manager::{GameManager, today},
+        puzzles::PuzzleDb,
+        save::{GameId, GameKind, SaveData},
+        server::{Faults, Server},
+    };
+
+    use super::*;
+
+    #[test]
+    fn round_trips_every_answer() {
+        let mut codes = std::collections::HashSet::new();
+        for &answer in ANSWERS.iter() {
+            let code = encode(answer).unwrap();
+            assert_eq!(code.len(), LENGTH);
+            assert!(!code.to_lowercase().contains(answer));
+            assert_eq!(decode(&code), Ok(answer));
+            codes.insert(code);
+        }
+        assert_eq!(codes.len(), ANSWERS.len());
+    }
+
+    #[test]
+    fn reads_codes_leniently() {
+        let code = encode("crane").unwrap();
+        let sloppy = format!("{}-{}", &code[..2], &code[2..]).to_lowercase();
+        assert_eq!(decode(&sloppy), Ok("crane"));
+        assert_eq!(normalize(&sloppy).unwrap(), code);
+        let confusable = code.replace('0', "o").replace('1', "l");
+        assert_eq!(decode(&confusable), Ok("crane"));
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        assert_eq!(encode("zzzzz"), Err(ChallengeError::NotAnAnswer));
+        assert_eq!(decode("ABCD"), Err(ChallengeError::Length));
+        assert_eq!(decode("ABCDU"), Err(ChallengeError::Character('U')));
+        assert_eq!(decode("ÄBCDE"), Err(ChallengeError::Character('Ä')));
+
+        // Most single-character typos are caught
+        let code = encode("crane").unwrap().into_bytes();
+        let mut caught = 0;
+        let mut typos = 0;
+        for i in 0..LENGTH {
+            for &digit in ALPHABET.iter().filter(|&&d| d != code[i]) {
+                let mut typo = code.clone();
+                typo[i] = digit;
+                typos += 1;
+                if decode(std::str::from_utf8(&typo).unwrap()).is_err() {
+                    caught += 1;
+                }
+            }
+        }
+        assert!(caught * 100 / typos >= 95, "caught {caught} of {typos}");
+    }
+
+    #[tokio::test]
+    async fn game_manager_saves_challenges_apart() {
+        let source = Server::generated(Faults::default()).spawn().await;
+        let mut game = GameManager::open(&source, SaveData::in_memory(), PuzzleDb::default())
+            .await
+            .unwrap();
+        let code = encode("crane").unwrap();
+
+        game.play_challenge(&code.to_lowercase()).unwrap();
+        assert_eq!(game.id, GameId::Challenge(code.clone()));
+        assert!(!game.is_latest());
+        game.guess("trace").unwrap();
+        game.save();
+
+        game.goto(today()).await.unwrap();
+        assert_eq!(game.id.kind(), GameKind::Daily);
+        assert_eq!(game.index, (0, 0));
+        assert_eq!(game.save_data.games_of(GameKind::Daily).count(), 0);
+
+        game.play_challenge(&code).unwrap();
+        assert_eq!(game.index, (1, 0));
+        game.guess("crane").unwrap();
+        assert_eq!(game.answer().as_deref(), Some("crane"));
+        assert!(game.play_challenge("ABCDU").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{LazyLock, OnceLock},
+};
+
+use anyhow::Context;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use directories::ProjectDirs;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{Message, theme::ThemeName, widget::KeyboardLayout};
+
+static CONFIG_PATH: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+    ProjectDirs::from("dev", "joee", "wordle").map(|dirs| dirs.config_dir().join("config.toml"))
+});
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+const HEADER: &str = "\
+# Configuration for wordle.
+#
+# Keys are written like \"Enter\", \"Ctrl+Left\", \"F1\" or \"?\". Letters that
+# aren't bound to anything below always type into the grid, and those under
+# `help_unless_typing` still do while guessing.
+# Themes are \"default\", \"high-contrast\", \"monochrome\" or \"light\". Any of
+# `green`, `yellow`, `gray` and `text` under [colors] override the theme, and are
+# named (\"green\", \"dark_gray\"), indexed (\"208\") or hex (\"#6aaa64\").
+# The on-screen keyboard is \"qwerty\", \"azerty\", \"qwertz\" or \"dvorak\".
+
+";
+
+pub(crate) fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+pub(crate) fn set(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    pub(crate) hard_mode: bool,
+    pub(crate) animations: bool,
+    pub(crate) source: String,
+    pub(crate) theme: ThemeName,
+    pub(crate) keyboard: KeyboardLayout,
+    pub(crate) colors: Colors,
+    pub(crate) keys: KeyMap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            hard_mode: false,
+            animations: true,
+            source: "https://www.nytimes.com/svc/wordle/v2".to_string(),
+            theme: ThemeName::default(),
+            keyboard: KeyboardLayout::default(),
+            colors: Colors::default(),
+            keys: KeyMap::default(),
+        }
+    }
+}
+
+impl Config {
+    pub(crate) fn load() -> anyhow::Result<Self> {
+        let Some(path) = CONFIG_PATH.as_ref() else {
+            return Ok(Self::default());
+        };
+
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Invalid config file {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let config = Self::default();
+                let _ = config.write(path);
+                Ok(config)
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, format!("{HEADER}{}", toml::to_string_pretty(self)?))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Colors {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) green: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) yellow: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) gray: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) text: Option<Color>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct KeyMap {
+    pub(crate) submit: Vec<KeyBinding>,
+    pub(crate) backspace: Vec<KeyBinding>,
+    pub(crate) next: Vec<KeyBinding>,
+    pub(crate) previous: Vec<KeyBinding>,
+    pub(crate) first: Vec<KeyBinding>,
+    pub(crate) last: Vec<KeyBinding>,
+    pub(crate) up: Vec<KeyBinding>,
+    pub(crate) down: Vec<KeyBinding>,
+    pub(crate) stats: Vec<KeyBinding>,
+    pub(crate) archive: Vec<KeyBinding>,
+    pub(crate) help: Vec<KeyBinding>,
+    /// Help keys that are ignored while typing a guess.
+    pub(crate) help_unless_typing: Vec<KeyBinding>,
+    pub(crate) escape: Vec<KeyBinding>,
+    pub(crate) quit: Vec<KeyBinding>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.parse().unwrap()).collect();
+
+        Self {
+            submit: keys(&["Enter"]),
+            backspace: keys(&["Backspace"]),
+            next: keys(&["Right"]),
+            previous: keys(&["Left"]),
+            first: keys(&["Ctrl+Left"]),
+            last: keys(&["Ctrl+Right"]),
+            up: keys(&["Up"]),
+            down: keys(&["Down"]),
+            stats: keys(&["?"]),
+            archive: keys(&["Tab"]),
+            help: keys(&["F1"]),
+            help_unless_typing: keys(&["h"]),
+            escape: keys(&["Esc"]),
+            quit: keys(&["Ctrl+C"]),
+        }
+    }
+}
+
+impl KeyMap {
+    pub(crate) fn actions(&self) -> [(&'static str, &[KeyBinding], Message); 14] {
+        [
+            ("Submit guess", &self.submit, Message::Submit),
+            ("Delete letter", &self.backspace, Message::Backspace),
+            ("Next puzzle", &self.next, Message::Next),
+            ("Previous puzzle", &self.previous, Message::Previous),
+            ("First puzzle", &self.first, Message::First),
+            ("Latest puzzle", &self.last, Message::Last),
+            ("Move up in lists", &self.up, Message::Up),
+            ("Move down in lists", &self.down, Message::Down),
+            ("Statistics", &self.stats, Message::Stats),
+            ("Archive", &self.archive, Message::Archive),
+            ("This help", &self.help, Message::Help),
+            (
+                "This help, unless typing",
+                &self.help_unless_typing,
+                Message::Help,
+            ),
+            ("Close window", &self.escape, Message::Escape),
+            ("Quit", &self.quit, Message::Quit),
+        ]
+    }
+
+    /// What a key press does. `typing` is whether letters go into a guess right now.
+    pub(crate) fn message(&self, event: &KeyEvent, typing: bool) -> Option<Message> {
+        let typed = typing && self.help_unless_typing.iter().any(|key| key.matches(event));
+        if !typed
+            && let Some((.., message)) = self
+                .actions()
+                .into_iter()
+                .find(|(_, keys, _)| keys.iter().any(|key| key.matches(event)))
+        {
+            return Some(message);
+        }
+
+        match event.code.as_char() {
+            Some(c)
+                if c.is_ascii_alphabetic() && !event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                Some(Message::Letter(c.to_ascii_uppercase()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn matches(&self, event: &KeyEvent) -> bool {
+        match (self.code, event.code) {
+            // Shift is implied by the character itself, e.g. `?`
+            (KeyCode::Char(a), KeyCode::Char(b)) => {
+                a.eq_ignore_ascii_case(&b)
+                    && self.modifiers.difference(KeyModifiers::SHIFT)
+                        == event.modifiers.difference(KeyModifiers::SHIFT)
+            }
+            (a, b) => a == b && self.modifiers == event.modifiers,
+        }
+    }
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("Space", KeyCode::Char(' ')),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifiers, key) = match s.rsplit_once('+') {
+            Some((modifiers, "")) => (modifiers.strip_suffix('+').unwrap_or(modifiers), "+"),
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", s),
+        };
+
+        let mut binding = KeyBinding {
+            code: KeyCode::Null,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            binding.modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{modifier}` in key `{s}`")),
+            };
+        }
+
+        binding.code = if let Some((_, code)) = NAMED_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        {
+            *code
+        } else if let Some(n) = key
+            .strip_prefix(['F', 'f'])
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|n| (1..=12).contains(n))
+        {
+            KeyCode::F(n)
+        } else {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c.to_ascii_lowercase()),
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        };
+
+        Ok(binding)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match self.code {
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => match NAMED_KEYS.iter().find(|(_, c)| *c == code) {
+                Some((name, _)) => f.write_str(name),
+                None => match code {
+                    KeyCode::Char(c) if self.modifiers.is_empty() => write!(f, "{c}"),
+                    KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+                    code => write!(f, "{code}"),
+                },
+            },
+        }
+    }
+}
+
+impl Serialize for KeyBinding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,376 @@
+use std::{error, fmt, sync::LazyLock, time::Duration};
+
+use chrono::NaiveDate;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use tokio::time;
+
+use crate::manager::wordle_number;
+
+const USER_AGENT: &str = concat!(
+    "wordle-rs/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/joeedev/wordle-rs)"
+);
+const TIMEOUT: Duration = Duration::from_secs(10);
+const BACKOFF: Duration = Duration::from_millis(500);
+const ATTEMPTS: u32 = 3;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(|| Client::new(TIMEOUT, BACKOFF));
+
+pub(crate) fn client() -> &'static Client {
+    &CLIENT
+}
+
+#[derive(Debug)]
+pub(crate) enum FetchError {
+    NotFound,
+    RateLimited,
+    /// Any other client error, e.g. 403 Forbidden.
+    Refused(StatusCode),
+    Network(reqwest::Error),
+    Decode(serde_json::Error),
+    /// The server sent a different puzzle, or numbered it differently, than expected for `date`.
+    Mismatch {
+        date: NaiveDate,
+        print_date: NaiveDate,
+        /// `None` if the puzzle came unnumbered and is dated before the first Wordle.
+        number: Option<u32>,
+    },
+}
+
+impl FetchError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            FetchError::RateLimited => true,
+            FetchError::Network(e) => e.status().is_none_or(|s| s.is_server_error()),
+            FetchError::NotFound
+            | FetchError::Refused(_)
+            | FetchError::Decode(_)
+            | FetchError::Mismatch { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NotFound => write!(f, "That Wordle isn't out yet"),
+            FetchError::RateLimited => write!(f, "Too many requests, try again later"),
+            FetchError::Refused(status) => {
+                write!(f, "The Wordle server refused the request ({status})")
+            }
+            FetchError::Network(e) if e.is_timeout() => write!(f, "The Wordle server timed out"),
+            FetchError::Network(_) => write!(f, "Couldn't reach the Wordle server"),
+            FetchError::Decode(_) => write!(f, "The Wordle server sent something unexpected"),
+            FetchError::Mismatch {
+                date,
+                print_date,
+                number,
+            } => write!(
+                f,
+                "Expected Wordle{} for {date}, but the server sent {} for {print_date}",
+                wordle_number(*date).map_or(String::new(), |n| format!(" #{n}")),
+                number.map_or("a puzzle".to_string(), |n| format!("#{n}")),
+            ),
+        }
+    }
+}
+
+impl error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            FetchError::Network(e) => Some(e),
+            FetchError::Decode(e) => Some(e),
+            FetchError::NotFound
+            | FetchError::RateLimited
+            | FetchError::Refused(_)
+            | FetchError::Mismatch { .. } => None,
+        }
+    }
+}
+
+pub(crate) struct Client {
+    http: reqwest::Client,
+    backoff: Duration,
+}
+
+impl Client {
+    pub(crate) fn new(timeout: Duration, backoff: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(timeout)
+            .read_timeout(timeout)
+            .build()
+            .expect("HTTP client should build");
+        Self { http, backoff }
+    }
+
+    /// Fetches and decodes `url`, retrying with exponential backoff on network errors, server
+    /// errors and rate limiting.
+    pub(crate) async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        let mut delay = self.backoff;
+        for _ in 1..ATTEMPTS {
+            match self.try_get_json(url).await {
+                Err(e) if e.is_retryable() => time::sleep(delay).await,
+                result => return result,
+            }
+            delay *= 2;
+        }
+        self.try_get_json(url).await
+    }
+
+    async fn try_get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        let res = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(FetchError::Network)?;
+
+        match res.status() {
+            StatusCode::NOT_FOUND => return Err(FetchError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => return Err(FetchError::RateLimited),
+            status if status.is_client_error() => return Err(FetchError::Refused(status)),
+            _ => {}
+        }
+
+        let body = res
+            .error_for_status()
+            .map_err(FetchError::Network)?
+            .bytes()
+            .await
+            .map_err(FetchError::Network)?;
+        serde_json::from_slice(&body).map_err(FetchError::Decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
+    use super::*;
+    use crate::wordle::GameInfo;
+
+    const PUZZLE: &str = r#"{"id":1,"solution":"crane","print_date":"2024-03-15","days_since_launch":1000,"editor":"Tracy Bennett"}"#;
+
+    fn client() -> Client {
+        Client::new(Duration::from_millis(200), Duration::from_millis(1))
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+    }
+
+    async fn fetch(server: &MockServer) -> Result<GameInfo, FetchError> {
+        GameInfo::fetch(&client(), &server.uri(), date()).await
+    }
+
+    #[tokio::test]
+    async fn fetches_puzzle() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2024-03-15.json"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string(PUZZLE))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let info = fetch(&server).await.unwrap();
+        assert_eq!(info.id, 1);
+        assert_eq!(info.word.reveal(), "crane");
+        assert_eq!(info.number, 1000);
+        assert_eq!(info.editor.as_deref(), Some("Tracy Bennett"));
+    }
+
+    #[tokio::test]
+    async fn numbers_puzzles_by_date_when_missing() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"solution":"crane","print_date":"2024-03-15"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let info = fetch(&server).await.unwrap();
+        assert_eq!(info.number, 1000);
+        assert_eq!(info.editor, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_mismatched_numbering() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"solution":"crane","print_date":"2024-03-15","days_since_launch":999}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            fetch(&server).await,
+            Err(FetchError::Mismatch {
+                number: Some(999),
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_puzzle_for_another_date() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(PUZZLE))
+            .mount(&server)
+            .await;
+
+        let next_day = date().succ_opt().unwrap();
+        let result = GameInfo::fetch(&client(), &server.uri(), next_day).await;
+        match result {
+            Err(e @ FetchError::Mismatch { .. }) => assert_eq!(
+                e.to_string(),
+                "Expected Wordle #1001 for 2024-03-16, but the server sent #1000 for 2024-03-15"
+            ),
+            result => panic!("expected a mismatch, got {result:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_puzzle_from_before_launch() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"solution":"crane","print_date":"2021-01-01"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        match fetch(&server).await {
+            Err(e @ FetchError::Mismatch { number: None, .. }) => assert_eq!(
+                e.to_string(),
+                "Expected Wordle #1000 for 2024-03-15, but the server sent a puzzle for 2021-01-01"
+            ),
+            result => panic!("expected a mismatch, got {result:?}"),
+        }
+
+        let before_launch = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let result = GameInfo::fetch(&client(), &server.uri(), before_launch).await;
+        match result {
+            Err(e @ FetchError::Mismatch { number: None, .. }) => assert_eq!(
+                e.to_string(),
+                "Expected Wordle for 2021-01-01, but the server sent a puzzle for 2021-01-01"
+            ),
+            result => panic!("expected a mismatch, got {result:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn not_found_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(matches!(fetch(&server).await, Err(FetchError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_after_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(u64::from(ATTEMPTS))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(fetch(&server).await, Err(FetchError::RateLimited)));
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(PUZZLE))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert_eq!(fetch(&server).await.unwrap().word.reveal(), "crane");
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let error = fetch(&server).await.unwrap_err();
+        assert!(matches!(error, FetchError::Refused(StatusCode::FORBIDDEN)));
+        assert_eq!(
+            error.to_string(),
+            "The Wordle server refused the request (403 Forbidden)"
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_body_is_decode_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>Not JSON</html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(matches!(fetch(&server).await, Err(FetchError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn slow_server_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(PUZZLE)
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .expect(u64::from(ATTEMPTS))
+            .mount(&server)
+            .await;
+
+        match fetch(&server).await {
+            Err(e @ FetchError::Network(_)) => {
+                assert_eq!(e.to_string(), "The Wordle server timed out")
+            }
+            result => panic!("expected a timeout, got {result:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_network_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let result = GameInfo::fetch(&client(), &uri, date()).await;
+        assert!(matches!(result, Err(FetchError::Network(_))));
+    }
+}
@@ -0,0 +1,518 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::TimeDelta;
+use clap::ValueEnum;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    widgets::{Row, Table, Widget},
+};
+
+use crate::{
+    SaveData,
+    manager::{date_to_wordle_number, today},
+    save::GameKind,
+    stats::Stats,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Format {
+    Table,
+    Markdown,
+    Csv,
+}
+
+/// One of the tables, to print on its own.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Section {
+    Standings,
+    Days,
+}
+
+pub(crate) struct Player {
+    name: String,
+    save_data: SaveData,
+}
+
+/// A player's finished game on one day.
+#[derive(Clone, Debug, PartialEq)]
+struct Outcome {
+    player: usize,
+    guesses: Option<usize>,
+    time: Option<TimeDelta>,
+}
+
+impl Outcome {
+    /// Solvers by fewest guesses then quickest, with untimed games after timed ones.
+    fn rank_key(&self) -> (usize, bool, TimeDelta) {
+        (
+            self.guesses.unwrap_or(usize::MAX),
+            self.time.is_none(),
+            self.time.unwrap_or_default(),
+        )
+    }
+}
+
+/// Every player's result for a puzzle, best first, with tied results sharing a rank.
+struct Day {
+    number: u32,
+    date: String,
+    results: Vec<(usize, Outcome)>,
+}
+
+struct Standing {
+    player: usize,
+    stats: Stats,
+    day_wins: usize,
+}
+
+impl Standing {
+    fn won(&self) -> usize {
+        self.stats.won.iter().sum()
+    }
+
+    fn average(&self) -> Option<f64> {
+        let guesses = (1..=6)
+            .zip(self.stats.won)
+            .map(|(n, won)| n * won)
+            .sum::<usize>();
+        (self.won() > 0).then(|| guesses as f64 / self.won() as f64)
+    }
+}
+
+pub(crate) struct Leaderboard {
+    players: Vec<Player>,
+    /// Most recent first.
+    days: Vec<Day>,
+    standings: Vec<Standing>,
+}
+
+impl Leaderboard {
+    pub(crate) fn new(players: Vec<Player>, latest: u32) -> Self {
+        let mut by_number = BTreeMap::<u32, (String, Vec<Outcome>)>::new();
+        for (i, player) in players.iter().enumerate() {
+            for game in player
+                .save_data
+                .games_of(GameKind::Daily)
+                .filter(|game| game.has_finished())
+            {
+                let (_, results) = by_number
+                    .entry(game.info.number)
+                    .or_insert_with(|| (game.info.date_string.clone(), Vec::new()));
+                results.push(Outcome {
+                    player: i,
+                    guesses: game.won_in(),
+                    time: game.time(),
+                });
+            }
+        }
+
+        let days = by_number
+            .into_iter()
+            .rev()
+            .map(|(number, (date, mut results))| {
+                results.sort_by_key(Outcome::rank_key);
+                let mut ranked = Vec::<(usize, Outcome)>::new();
+                for (i, result) in results.into_iter().enumerate() {
+                    let rank = match ranked.last() {
+                        Some((rank, last)) if last.rank_key() == result.rank_key() => *rank,
+                        _ => i + 1,
+                    };
+                    ranked.push((rank, result));
+                }
+                Day {
+                    number,
+                    date,
+                    results: ranked,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut standings = players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| Standing {
+                player: i,
+                stats: player.save_data.stats_as_of(GameKind::Daily, latest),
+                day_wins: days
+                    .iter()
+                    .flat_map(|day| &day.results)
+                    .filter(|(rank, result)| {
+                        *rank == 1 && result.player == i && result.guesses.is_some()
+                    })
+                    .count(),
+            })
+            .collect::<Vec<_>>();
+        standings.sort_by(|a, b| {
+            (Reverse(a.day_wins), Reverse(a.won()))
+                .cmp(&(Reverse(b.day_wins), Reverse(b.won())))
+                .then(
+                    a.average()
+                        .unwrap_or(7.0)
+                        .total_cmp(&b.average().unwrap_or(7.0)),
+                )
+        });
+
+        Self {
+            players,
+            days,
+            standings,
+        }
+    }
+
+    fn name(&self, player: usize) -> &str {
+        &self.players[player].name
+    }
+
+    fn standings_table(&self) -> Vec<Vec<String>> {
+        let header = [
+            "Rank", "Player", "Played", "Won", "Average", "Day wins", "Streak", "Best",
+        ];
+        let rows = self.standings.iter().enumerate().map(|(i, standing)| {
+            vec![
+                (i + 1).to_string(),
+                self.name(standing.player).to_string(),
+                standing.stats.attempted.to_string(),
+                standing.won().to_string(),
+                standing
+                    .average()
+                    .map_or("-".to_string(), |average| format!("{average:.2}")),
+                standing.day_wins.to_string(),
+                standing.stats.current_streak.to_string(),
+                standing.stats.max_streak.to_string(),
+            ]
+        });
+
+        [header.map(String::from).to_vec()]
+            .into_iter()
+            .chain(rows)
+            .collect()
+    }
+
+    fn days_table(&self, count: usize) -> Vec<Vec<String>> {
+        let header = ["No.", "Date"]
+            .into_iter()
+            .map(String::from)
+            .chain(self.players.iter().map(|player| player.name.clone()))
+            .collect();
+        let rows = self.days.iter().take(count).map(|day| {
+            let mut row = vec![format!("#{}", day.number), day.date.clone()];
+            row.extend((0..self.players.len()).map(|player| {
+                day.results
+                    .iter()
+                    .find(|(_, result)| result.player == player)
+                    .map_or("-".to_string(), |(rank, result)| {
+                        format_outcome(*rank, result)
+                    })
+            }));
+            row
+        });
+
+        [header].into_iter().chain(rows).collect()
+    }
+
+    /// Both tables with headings, or just one. CSV only ever holds one, the standings by default.
+    fn report(&self, format: Format, only: Option<Section>, days: usize) -> String {
+        let print = |table: &[Vec<String>]| match format {
+            Format::Table => render(table),
+            Format::Markdown => markdown(table),
+            Format::Csv => csv(table),
+        };
+
+        let only = only.or((format == Format::Csv).then_some(Section::Standings));
+        match only {
+            Some(Section::Standings) => print(&self.standings_table()),
+            Some(Section::Days) => print(&self.days_table(days)),
+            None => {
+                let mut report = format!("Standings\n\n{}", print(&self.standings_table()));
+                if days > 0 {
+                    report +=
+                        &format!("\nLast {days} puzzles\n\n{}", print(&self.days_table(days)));
+                }
+                report
+            }
+        }
+    }
+}
+
+fn format_outcome(rank: usize, outcome: &Outcome) -> String {
+    let guesses = outcome
+        .guesses
+        .map_or("X".to_string(), |guesses| guesses.to_string());
+    match outcome.time {
+        Some(time) => {
+            let seconds = time.num_seconds().max(0);
+            format!("{rank}. {guesses}/6 {}:{:02}", seconds / 60, seconds % 60)
+        }
+        None => format!("{rank}. {guesses}/6"),
+    }
+}
+
+fn markdown(table: &[Vec<String>]) -> String {
+    let line = |cells: &[String]| format!("| {} |\n", cells.join(" | "));
+    let mut out = String::new();
+    if let Some((header, rows)) = table.split_first() {
+        out += &line(header);
+        out += &line(&vec!["---".to_string(); header.len()]);
+        for row in rows {
+            out += &line(row);
+        }
+    }
+    out
+}
+
+fn csv(table: &[Vec<String>]) -> String {
+    let field = |cell: &String| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+    table
+        .iter()
+        .map(|row| row.iter().map(field).collect::<Vec<_>>().join(",") + "\n")
+        .collect()
+}
+
+/// Renders a ratatui table to plain text lines, sized to fit its contents.
+fn render(table: &[Vec<String>]) -> String {
+    let Some((header, rows)) = table.split_first() else {
+        return String::new();
+    };
+    let widths = (0..header.len())
+        .map(|col| {
+            table
+                .iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0) as u16
+        })
+        .collect::<Vec<_>>();
+
+    let area = Rect::new(
+        0,
+        0,
+        widths.iter().sum::<u16>() + widths.len().saturating_sub(1) as u16,
+        table.len() as u16,
+    );
+    let mut buf = Buffer::empty(area);
+    Table::new(
+        rows.iter().map(|row| Row::new(row.clone())),
+        widths.iter().map(|&width| Constraint::Length(width)),
+    )
+    .header(Row::new(header.clone()).bold())
+    .render(area, &mut buf);
+
+    (0..area.height)
+        .map(|y| {
+            let line = (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>();
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+/// Save files in `paths`, looking inside directories for `.dat` files.
+fn save_files(paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut found = fs::read_dir(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "dat"))
+            .collect::<Vec<_>>();
+        found.sort();
+        files.extend(found);
+    }
+    Ok(files)
+}
+
+fn load(path: &Path) -> anyhow::Result<Player> {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .with_context(|| format!("Failed to name the player for {}", path.display()))?;
+    Ok(Player {
+        name,
+        save_data: SaveData::from_path(path)?,
+    })
+}
+
+pub(crate) fn run(
+    paths: &[PathBuf],
+    format: Option<Format>,
+    only: Option<Section>,
+    days: usize,
+) -> anyhow::Result<()> {
+    let files = save_files(paths)?;
+    anyhow::ensure!(!files.is_empty(), "No save files found");
+    let players = files
+        .iter()
+        .map(|path| load(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let leaderboard = Leaderboard::new(players, date_to_wordle_number(today()));
+    let format = format.unwrap_or(if io::stdout().is_terminal() {
+        Format::Table
+    } else {
+        Format::Markdown
+    });
+    print!("{}", leaderboard.report(format, only, days));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        save::GameId,
+        wordle::{Answer, Game, GameInfo},
+    };
+
+    fn game(number: u32, guesses: &[&str], seconds: Option<i64>) -> Game {
+        let mut game = Game::from(GameInfo {
+            id: 0,
+            number,
+            word: Answer::new("crane"),
+            date_string: format!("2024-03-{:02}", number - 985),
+            editor: None,
+        });
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game.started = None;
+        if let Some(seconds) = seconds {
+            game.started = Some(game.finished.unwrap() - Duration::seconds(seconds));
+        }
+        game
+    }
+
+    fn player(name: &str, games: &[Game]) -> Player {
+        let mut save_data = SaveData::in_memory();
+        for game in games {
+            save_data.save(GameId::Daily(game.info.number), game);
+        }
+        Player {
+            name: name.to_string(),
+            save_data,
+        }
+    }
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard::new(
+            vec![
+                player(
+                    "alice",
+                    &[
+                        game(999, &["trace", "crane"], Some(95)),
+                        game(1000, &["trace", "crane"], Some(30)),
+                    ],
+                ),
+                player(
+                    "bob",
+                    &[
+                        game(999, &["crane"], None),
+                        game(1000, &["slate", "trace", "pious", "crane"], Some(20)),
+                    ],
+                ),
+                player(
+                    "carol",
+                    &[
+                        game(999, &["trace", "crane"], Some(40)),
+                        game(1000, &["trace", "crane"], Some(30)),
+                        game(1001, &["slate"; 6], Some(300)),
+                    ],
+                ),
+            ],
+            1001,
+        )
+    }
+
+    #[test]
+    fn ranks_days_by_guesses_then_time() {
+        let leaderboard = leaderboard();
+        assert_eq!(
+            leaderboard.days_table(3),
+            [
+                ["No.", "Date", "alice", "bob", "carol"].map(String::from),
+                ["#1001", "2024-03-16", "-", "-", "1. X/6 5:00"].map(String::from),
+                [
+                    "#1000",
+                    "2024-03-15",
+                    "1. 2/6 0:30",
+                    "3. 4/6 0:20",
+                    "1. 2/6 0:30"
+                ]
+                .map(String::from),
+                ["#999", "2024-03-14", "3. 2/6 1:35", "1. 1/6", "2. 2/6 0:40"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn totals_standings() {
+        let leaderboard = leaderboard();
+        assert_eq!(
+            leaderboard.standings_table(),
+            [
+                [
+                    "Rank", "Player", "Played", "Won", "Average", "Day wins", "Streak", "Best"
+                ],
+                ["1", "alice", "2", "2", "2.00", "1", "2", "2"],
+                ["2", "carol", "3", "2", "2.00", "1", "0", "2"],
+                ["3", "bob", "2", "2", "2.50", "1", "2", "2"],
+            ]
+            .map(|row| row.map(String::from).to_vec())
+        );
+    }
+
+    #[test]
+    fn prints_either_table_as_csv() {
+        let leaderboard = leaderboard();
+        let standings = leaderboard.report(Format::Csv, None, 3);
+        assert_eq!(standings, csv(&leaderboard.standings_table()));
+        assert_eq!(
+            leaderboard.report(Format::Csv, Some(Section::Standings), 3),
+            standings
+        );
+
+        let days = leaderboard.report(Format::Csv, Some(Section::Days), 3);
+        assert_eq!(days, csv(&leaderboard.days_table(3)));
+        assert!(days.starts_with("No.,Date,alice,bob,carol\n#1001,"));
+
+        let both = leaderboard.report(Format::Markdown, None, 3);
+        assert!(both.starts_with("Standings\n\n|"));
+        assert!(both.contains("\nLast 3 puzzles\n\n| No. |"));
+    }
+
+    #[test]
+    fn formats() {
+        let table = [["Player", "Note"], ["alice", "fast, \"lucky\""]]
+            .map(|row| row.map(String::from).to_vec());
+        assert_eq!(
+            markdown(&table),
+            "| Player | Note |\n| --- | --- |\n| alice | fast, \"lucky\" |\n"
+        );
+        assert_eq!(csv(&table), "Player,Note\nalice,\"fast, \"\"lucky\"\"\"\n");
+        assert_eq!(render(&table), "Player Note\nalice  fast, \"lucky\"\n");
+    }
+}
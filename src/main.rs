@@ -1,20 +1,231 @@
+mod animation;
+mod archive;
+mod bench;
+mod bot;
+mod challenge;
+mod config;
+mod fetch;
+mod leaderboard;
 mod manager;
+mod opener;
+mod plain;
+mod puzzles;
+mod race;
 mod save;
+mod server;
+mod solver;
+mod spectate;
 mod stats;
+mod theme;
 mod widget;
 mod wordle;
 
-use crossterm::event::{self, Event, KeyModifiers};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
+
+use animation::{Animation, Effect};
+use archive::Archive;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use config::{Config, config};
+use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    execute,
+};
+use fetch::FetchError;
+use futures::StreamExt;
 use manager::GameManager;
 use ratatui::{
     Frame,
-    layout::{Constraint, Flex, Layout, Rect},
+    layout::{Constraint, Flex, Layout, Position, Rect},
     style::Stylize,
     text::Line,
     widgets::{Block, Clear, Padding},
 };
 use save::SaveData;
+use solver::Solver;
 use stats::Stats;
+use tokio::time::{self, MissedTickBehavior};
+use widget::{Board, Help, Key, Target};
+
+const TICK_RATE: Duration = Duration::from_millis(33);
+const CLOCK_RATE: Duration = Duration::from_secs(1);
+
+/// Wordle in the terminal, with the real daily puzzles
+#[derive(Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Play line by line on stdin and stdout instead of full screen.
+    /// This is the default when stdout isn't a terminal
+    #[arg(long)]
+    plain: bool,
+
+    /// Show scored rows as emoji in plain mode
+    #[arg(long)]
+    emoji: bool,
+
+    /// Play by exchanging newline-delimited JSON on stdin and stdout, e.g.
+    /// {"guess":"crane"}. Games played this way aren't saved
+    #[arg(long)]
+    bot: bool,
+
+    /// Base URL to fetch puzzles from, instead of the one in the config file
+    #[arg(long, global = true)]
+    source: Option<String>,
+
+    /// Puzzle to play, as YYYY-MM-DD (defaults to today's)
+    #[arg(long, value_parser = parse_date)]
+    date: Option<NaiveDate>,
+
+    /// Mirror the game to spectators connecting to this address, for `wordle watch`
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = spectate::DEFAULT_ADDRESS,
+        conflicts_with_all = ["plain", "bot"]
+    )]
+    publish: Option<String>,
+
+    /// Send spectators only the colors of your guesses, never the letters
+    #[arg(long, requires = "publish")]
+    hide_letters: bool,
+
+    /// Practice on a secret answer picked from this seed instead of a real puzzle
+    #[arg(long, conflicts_with = "date")]
+    seed: Option<u64>,
+
+    /// Play a challenge someone made with `wordle challenge`
+    #[arg(long, conflicts_with_all = ["date", "seed"])]
+    challenge: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play every answer in the built-in answer list with a solver and report how it did
+    Bench {
+        #[arg(long, value_enum, default_value_t = Solver::Entropy)]
+        solver: Solver,
+
+        /// First guess to use instead of the solver's own pick
+        #[arg(long)]
+        opener: Option<String>,
+    },
+
+    /// Show how well a first guess splits up the answer list, and how it went for you
+    Opener { word: String },
+
+    /// Make a challenge code for a word from the answer list, for someone else to play
+    Challenge { word: String },
+
+    /// Rank every allowed guess as an opener, alongside the ones you've used
+    Openers {
+        /// How many openers to show
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Serve puzzles locally in the NYT's format, for development and testing
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// JSON file with an array of puzzles to serve. Without one, a made-up puzzle is served
+        /// for every date up to today
+        #[arg(long)]
+        archive: Option<PathBuf>,
+
+        /// Milliseconds to wait before each response
+        #[arg(long, default_value_t = 0)]
+        latency: u64,
+
+        /// Answer every request with a 404
+        #[arg(long)]
+        not_found: bool,
+
+        /// Answer every request with malformed JSON
+        #[arg(long)]
+        malformed: bool,
+    },
+
+    /// Host a race on the local network, and play in it. Everyone plays the same puzzle
+    Host {
+        #[arg(long, default_value_t = 7777)]
+        port: u16,
+
+        /// Race on an answer picked from this seed instead of today's puzzle
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Name to show the other players (defaults to your username)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Join a race someone is hosting
+    Join {
+        /// The host's address, e.g. 192.168.1.20:7777
+        address: String,
+
+        /// Name to show the other players (defaults to your username)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Watch a game someone is playing with --publish, read-only
+    Watch {
+        #[arg(default_value = spectate::DEFAULT_ADDRESS)]
+        address: String,
+
+        /// Show only the colors, without the letters
+        #[arg(long)]
+        hide_letters: bool,
+    },
+
+    /// Write your save file somewhere, e.g. to share for `wordle leaderboard`
+    Export {
+        /// Where to write it (defaults to <username>.dat). The file name is used as your name on
+        /// leaderboards
+        path: Option<PathBuf>,
+    },
+
+    /// Rank players by their exported save files, day by day and overall
+    Leaderboard {
+        /// Save files, or directories of them
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Output format (defaults to a table on a terminal and markdown otherwise)
+        #[arg(long, value_enum)]
+        format: Option<leaderboard::Format>,
+
+        /// Print only one table. CSV holds one table, the standings unless this picks days
+        #[arg(long, value_enum)]
+        only: Option<leaderboard::Section>,
+
+        /// How many of the latest puzzles to show results for
+        #[arg(long, default_value_t = 7)]
+        days: usize,
+    },
+
+    /// Download every puzzle so far, so past puzzles can be played without a connection.
+    /// Picks up where it left off if interrupted
+    SyncArchive {
+        /// How many puzzles to fetch at once
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+}
 
 fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
     let [area] = Layout::horizontal([horizontal])
@@ -40,132 +251,471 @@ enum Message {
     Previous,
     First,
     Last,
+    Up,
+    Down,
 
     Stats,
+    Archive,
+    Help,
     Escape,
     Quit,
+
+    Tick,
+}
+
+fn animate(animations: &mut Vec<Animation>, animation: Animation) {
+    if config().animations {
+        animations.push(animation);
+    }
+}
+
+fn add_char(game: &mut wordle::Game, animations: &mut Vec<Animation>, char: char) {
+    let index = game.index;
+    game.add_char(char);
+    if game.index != index {
+        animate(animations, Animation::new(index.0, Effect::Pop(index.1)));
+    }
+}
+
+/// Submits the current row and animates it, returning the error to show if it was rejected.
+fn submit(game: &mut wordle::Game, animations: &mut Vec<Animation>) -> Option<String> {
+    let row = game.index.0;
+    match game.submit() {
+        Ok(()) if game.index.0 > row => {
+            let reveal = Animation::new(row, Effect::Reveal);
+            if game.won_in() == Some(row + 1) {
+                animate(
+                    animations,
+                    Animation::new(row, Effect::Bounce).after(reveal.duration()),
+                );
+            }
+            animate(animations, reveal);
+            None
+        }
+        Ok(()) => None,
+        Err(e) => {
+            animate(animations, Animation::new(row, Effect::Shake));
+            Some(e.to_string())
+        }
+    }
+}
+
+enum Overlay {
+    Stats(Stats, u16),
+    Archive(Archive),
+    Help(u16),
 }
 
 struct Model {
     game: GameManager,
-    stats: Option<Stats>,
+    area: Rect,
+    overlay: Option<Overlay>,
+    notice: Option<String>,
+    animations: Vec<Animation>,
     running_state: RunningState,
 }
 
 impl Model {
-    async fn new() -> Self {
-        Self {
-            game: GameManager::new().await.expect("game should be fetched"),
-            stats: None,
+    async fn new(
+        date: Option<NaiveDate>,
+        seed: Option<u64>,
+        challenge: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let game = GameManager::start(date, seed, challenge).await?;
+        let overlay = game.save_data.is_empty().then_some(Overlay::Help(0));
+
+        Ok(Self {
+            game,
+            area: Rect::default(),
+            overlay,
+            notice: None,
+            animations: Vec::new(),
             running_state: RunningState::Running,
+        })
+    }
+
+    fn report(&mut self, result: Result<(), FetchError>) {
+        if let Err(e) = result {
+            self.notice = Some(e.to_string());
+        }
+    }
+
+    /// Whether letters go into a guess, rather than being free for other keys.
+    fn typing(&self) -> bool {
+        self.overlay.is_none() && !self.game.has_finished()
+    }
+
+    fn mouse(&self, event: MouseEvent) -> Option<Message> {
+        let position = Position::new(event.column, event.row);
+
+        match (event.kind, &self.overlay) {
+            (MouseEventKind::ScrollUp, Some(_)) => Some(Message::Up),
+            (MouseEventKind::ScrollDown, Some(_)) => Some(Message::Down),
+            (MouseEventKind::Down(MouseButton::Left), None) => {
+                let board = Board {
+                    game: &self.game,
+                    notice: None,
+                    animations: &[],
+                    countdown: None,
+                };
+                match board.hit(self.area, position)? {
+                    Target::Key(Key::Letter(letter)) => Some(Message::Letter(letter.char)),
+                    Target::Key(Key::Enter) => Some(Message::Submit),
+                    Target::Key(Key::Backspace) => Some(Message::Backspace),
+                    Target::Previous => Some(Message::Previous),
+                    Target::Next => Some(Message::Next),
+                }
+            }
+            _ => None,
         }
     }
 
     async fn update(&mut self, msg: Message) {
-        match msg {
-            Message::Letter(char) => {
-                self.game.add_char(char);
+        if let Message::Tick = msg {
+            self.animations.retain(|a| !a.is_done());
+            self.game.check_rollover();
+            return;
+        }
+
+        if let Some(Overlay::Archive(archive)) = &mut self.overlay {
+            match msg {
+                Message::Up => archive.up(),
+                Message::Down => archive.down(),
+                Message::First => archive.top(),
+                Message::Last => archive.bottom(),
+                Message::Next => archive.next_filter(),
+                Message::Previous => archive.previous_filter(),
+                Message::Submit => {
+                    if let Some(date) = archive.selected() {
+                        self.overlay = None;
+                        self.animations.clear();
+                        let result = self.game.goto(date).await;
+                        self.report(result);
+                    }
+                }
+                Message::Archive | Message::Escape => self.overlay = None,
+                Message::Help => self.overlay = Some(Overlay::Help(0)),
+                Message::Quit => self.running_state = RunningState::Done,
+                _ => {}
             }
+            return;
+        }
+
+        self.notice = None;
+
+        if let Message::Next | Message::Previous | Message::First | Message::Last = msg {
+            self.animations.clear();
+        }
+
+        match msg {
+            Message::Letter(char) => add_char(&mut self.game, &mut self.animations, char),
             Message::Backspace => {
                 self.game.backspace();
             }
+            Message::Submit if self.game.rolled_over && self.game.has_finished() => {
+                let result = self.game.last().await;
+                self.report(result);
+            }
             Message::Submit => {
-                self.game.submit();
+                self.notice = submit(&mut self.game, &mut self.animations);
             }
 
             Message::Next => {
-                self.game.next().await;
+                let result = self.game.next().await;
+                self.report(result);
             }
             Message::Previous => {
-                self.game.previous().await;
+                let result = self.game.previous().await;
+                self.report(result);
             }
 
             Message::First => {
-                self.game.first().await;
+                let result = self.game.first().await;
+                self.report(result);
             }
             Message::Last => {
-                self.game.last().await;
+                let result = self.game.last().await;
+                self.report(result);
+            }
+            Message::Up => {
+                if let Some(Overlay::Stats(_, scroll) | Overlay::Help(scroll)) = &mut self.overlay {
+                    *scroll = scroll.saturating_sub(1);
+                }
+            }
+            Message::Down => {
+                if let Some(Overlay::Stats(_, scroll) | Overlay::Help(scroll)) = &mut self.overlay {
+                    *scroll += 1;
+                }
             }
 
             Message::Stats => {
-                if self.stats.is_some() {
-                    self.stats = None;
-                } else {
-                    self.stats = Some(self.game.stats());
-                }
+                self.overlay = match self.overlay {
+                    Some(Overlay::Stats(..)) => None,
+                    _ => Some(Overlay::Stats(self.game.stats(), 0)),
+                };
+            }
+            Message::Archive => {
+                self.overlay = Some(Overlay::Archive(self.game.archive()));
+            }
+            Message::Help => {
+                self.overlay = match self.overlay {
+                    Some(Overlay::Help(_)) => None,
+                    _ => Some(Overlay::Help(0)),
+                };
             }
             Message::Escape => {
-                self.stats = None;
+                self.overlay = None;
             }
 
             Message::Quit => {
                 self.running_state = RunningState::Done;
             }
+
+            Message::Tick => {}
         }
 
         self.game.save();
     }
 
-    fn view(&self, frame: &mut Frame) {
-        let game: &wordle::Game = &self.game;
-        frame.render_widget(game, frame.area());
+    fn view(&mut self, frame: &mut Frame) {
+        self.area = frame.area();
 
-        if let Some(stats) = &self.stats {
-            let block = Block::bordered()
-                .title_top(Line::from(" Statistics ").bold().centered())
-                .padding(Padding::uniform(1));
+        let rollover = self.game.rolled_over.then(|| {
+            let key = config().keys.last.first().map(ToString::to_string);
+            format!(
+                "A new Wordle is out! Press {} to play it",
+                key.as_deref().unwrap_or("Enter")
+            )
+        });
 
-            let area = center(frame.area(), Constraint::Max(50), Constraint::Max(18));
+        let board = Board {
+            game: &self.game,
+            notice: self.notice.as_deref().or(rollover.as_deref()),
+            animations: &self.animations,
+            countdown: (self.game.is_latest() && self.game.has_finished())
+                .then(manager::until_tomorrow),
+        };
+        frame.render_widget(board, frame.area());
 
-            frame.render_widget(Clear, area);
-            frame.render_widget(&block, area);
-            frame.render_widget(stats, block.inner(area));
+        if !Board::fits(frame.area()) {
+            return;
+        }
+
+        let help = Help {
+            keys: &config().keys,
+        };
+        let (title, width, height) = match &self.overlay {
+            Some(Overlay::Stats(stats, _)) => {
+                (" Statistics ", 50, Constraint::Max(stats.height() + 4))
+            }
+            Some(Overlay::Archive(_)) => (" Archive ", 50, Constraint::Percentage(80)),
+            Some(Overlay::Help(_)) => (" How To Play ", 60, Constraint::Max(help.height() + 2)),
+            None => return,
+        };
+
+        let area = center(frame.area(), Constraint::Max(width), height);
+        // Padding is the first thing to go on small terminals
+        let padding = if area.width < width {
+            Padding::ZERO
+        } else {
+            Padding::horizontal(1)
+        };
+        let block = Block::bordered()
+            .title_top(Line::from(title).bold().centered())
+            .padding(padding);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(&block, area);
+
+        match &mut self.overlay {
+            Some(Overlay::Stats(stats, scroll)) => {
+                let block = if area.height >= stats.height() + 4 {
+                    block.padding(Padding::uniform(1))
+                } else {
+                    block
+                };
+                widget::render_scrolled(
+                    &*stats,
+                    stats.height(),
+                    scroll,
+                    block.inner(area),
+                    frame.buffer_mut(),
+                )
+            }
+            Some(Overlay::Archive(archive)) => frame.render_widget(archive, block.inner(area)),
+            Some(Overlay::Help(scroll)) => {
+                let height = help.height();
+                widget::render_scrolled(help, height, scroll, block.inner(area), frame.buffer_mut())
+            }
+            None => {}
         }
     }
 }
 
+/// Parses `--date`, which can't be before the first Wordle.
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    let date: NaiveDate = s.parse().map_err(|e| format!("{e}"))?;
+    if date < manager::FIRST_WORDLE_DATE {
+        return Err(format!(
+            "the first Wordle was on {}",
+            manager::FIRST_WORDLE_DATE
+        ));
+    }
+    Ok(date)
+}
+
+/// Names race players and exported save files.
+fn username() -> String {
+    env::var("USER").unwrap_or_else(|_| "Player".to_string())
+}
+
+fn or_exit<T>(result: anyhow::Result<T>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Error: {e:?}");
+        process::exit(1);
+    })
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
+    let cli = Cli::parse();
+
+    let mut loaded = or_exit(Config::load());
+    if let Some(source) = cli.source {
+        loaded.source = source;
+    }
+    config::set(loaded);
+
+    match cli.command {
+        Some(Command::Bench { solver, opener }) => return or_exit(bench::run(solver, opener)),
+        Some(Command::Challenge { word }) => return or_exit(challenge::run(&word)),
+        Some(Command::Opener { word }) => return or_exit(opener::run(&word)),
+        Some(Command::Openers { top }) => return or_exit(opener::run_top(top)),
+        Some(Command::Serve {
+            port,
+            archive,
+            latency,
+            not_found,
+            malformed,
+        }) => {
+            let faults = server::Faults {
+                latency: Duration::from_millis(latency),
+                not_found,
+                malformed,
+            };
+            return or_exit(server::run(port, archive.as_deref(), faults).await);
+        }
+        Some(Command::Host { port, seed, name }) => {
+            let name = name.unwrap_or_else(username);
+            return or_exit(race::host(port, seed, &name).await);
+        }
+        Some(Command::Join { address, name }) => {
+            let name = name.unwrap_or_else(username);
+            return or_exit(race::join(&address, &name).await);
+        }
+        Some(Command::Watch {
+            address,
+            hide_letters,
+        }) => return or_exit(spectate::watch(&address, hide_letters).await),
+        Some(Command::Export { path }) => {
+            let path = path.unwrap_or_else(|| format!("{}.dat", username()).into());
+            return or_exit(SaveData::read_only().and_then(|save_data| save_data.export(&path)));
+        }
+        Some(Command::Leaderboard {
+            paths,
+            format,
+            only,
+            days,
+        }) => return or_exit(leaderboard::run(&paths, format, only, days)),
+        Some(Command::SyncArchive { concurrency }) => {
+            return or_exit(puzzles::run(concurrency).await);
+        }
+        None => {}
+    }
+    if cli.bot {
+        return or_exit(bot::run(cli.date, cli.seed, cli.challenge.as_deref()).await);
+    }
+    if cli.plain || !io::stdout().is_terminal() {
+        return or_exit(plain::run(cli.date, cli.seed, cli.challenge.as_deref(), cli.emoji).await);
+    }
+
+    let mut model = or_exit(Model::new(cli.date, cli.seed, cli.challenge.as_deref()).await);
+    let publisher = match &cli.publish {
+        Some(addr) => Some(or_exit(
+            spectate::Publisher::bind(addr, &model.game, cli.hide_letters).await,
+        )),
+        None => None,
+    };
     let mut terminal = ratatui::init();
-    let mut model = Model::new().await;
+    execute!(io::stdout(), EnableMouseCapture).expect("failed to enable mouse capture");
+
+    let mut events = EventStream::new();
+    let mut ticks = time::interval(TICK_RATE);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut clock = time::interval(CLOCK_RATE);
+    clock.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
     while model.running_state == RunningState::Running {
         terminal
             .draw(|f| model.view(f))
             .expect("failed to draw frame");
 
-        let message = match event::read().expect("failed to read event") {
-            Event::Key(e) if e.code.is_backspace() => Some(Message::Backspace),
-            Event::Key(e) if e.code.is_enter() => Some(Message::Submit),
+        let message = tokio::select! {
+            _ = ticks.tick(), if !model.animations.is_empty() => Some(Message::Tick),
+            _ = clock.tick() => Some(Message::Tick),
+            event = events.next() => match event {
+                Some(event) => match event.expect("failed to read event") {
+                    Event::Key(e) if e.is_press() => config().keys.message(&e, model.typing()),
+                    Event::Mouse(e) => model.mouse(e),
+                    _ => None,
+                },
+                None => Some(Message::Quit),
+            },
+        };
 
-            Event::Key(e) if e.code.is_left() && e.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(Message::First)
-            }
-            Event::Key(e) if e.code.is_right() && e.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(Message::Last)
+        if let Some(message) = message {
+            model.update(message).await;
+            if let Some(publisher) = &publisher {
+                publisher.publish(&model.game);
             }
+        }
+    }
+    let _ = execute!(io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+}
 
-            Event::Key(e) if e.code.is_left() => Some(Message::Previous),
-            Event::Key(e) if e.code.is_right() => Some(Message::Next),
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
 
-            Event::Key(e) if e.code.is_char('c') && e.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(Message::Quit)
-            }
+    use super::*;
+    use crate::config::KeyMap;
 
-            Event::Key(e) if e.code.is_char('?') => Some(Message::Stats),
-            Event::Key(e) if e.code.is_esc() => Some(Message::Escape),
+    #[test]
+    fn h_opens_help_unless_typing() {
+        let keys = KeyMap::default();
+        let h = KeyEvent::from(KeyCode::Char('h'));
+        assert!(matches!(keys.message(&h, false), Some(Message::Help)));
+        assert!(matches!(keys.message(&h, true), Some(Message::Letter('H'))));
+        assert!(matches!(
+            keys.message(&KeyEvent::from(KeyCode::F(1)), true),
+            Some(Message::Help)
+        ));
+    }
 
-            Event::Key(e) => match e.code.as_char() {
-                Some(c) if c.is_alphabetic() => Some(Message::Letter(c.to_ascii_uppercase())),
-                _ => None,
-            },
+    #[test]
+    fn rejects_dates_before_the_first_wordle() {
+        let cli = Cli::try_parse_from(["wordle", "--date", "2021-06-19"]).unwrap();
+        assert_eq!(cli.date, Some(manager::FIRST_WORDLE_DATE));
 
-            _ => None,
+        let Err(error) = Cli::try_parse_from(["wordle", "--plain", "--date", "2021-06-18"]) else {
+            panic!("accepted a date before the first Wordle");
         };
-
-        if let Some(message) = message {
-            model.update(message).await;
-        }
+        assert!(
+            error
+                .to_string()
+                .contains("the first Wordle was on 2021-06-19")
+        );
+        assert!(Cli::try_parse_from(["wordle", "--date", "yesterday"]).is_err());
     }
-    ratatui::restore();
 }
@@ -1,80 +1,220 @@
 use std::ops::{Deref, DerefMut};
 
-use chrono::{Duration, NaiveDate, Utc};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
 
-use crate::{SaveData, Stats, wordle};
+use anyhow::Context;
 
-const FIRST_WORDLE_DATE: NaiveDate = NaiveDate::from_ymd_opt(2021, 6, 19).unwrap();
+use crate::{
+    SaveData, Stats,
+    archive::Archive,
+    challenge::{self, ChallengeError},
+    config::config,
+    fetch::FetchError,
+    puzzles::PuzzleDb,
+    save::{GameId, GameKind},
+    wordle,
+};
 
-fn date_to_wordle_number(date: NaiveDate) -> u32 {
-    (date - FIRST_WORDLE_DATE).num_days().try_into().unwrap()
+pub(crate) const FIRST_WORDLE_DATE: NaiveDate = NaiveDate::from_ymd_opt(2021, 6, 19).unwrap();
+
+pub(crate) fn date_to_wordle_number(date: NaiveDate) -> u32 {
+    wordle_number(date).expect("date is before the first Wordle")
+}
+
+/// The number of the puzzle for `date`, or `None` if it's before the first Wordle.
+pub(crate) fn wordle_number(date: NaiveDate) -> Option<u32> {
+    (date - FIRST_WORDLE_DATE).num_days().try_into().ok()
+}
+
+pub(crate) fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+pub(crate) fn until_tomorrow() -> Duration {
+    let midnight = (today() + Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    midnight - Utc::now()
 }
 
 pub(crate) struct GameManager {
     game: wordle::Game,
+    source: String,
+    puzzles: PuzzleDb,
     pub(crate) date: NaiveDate,
+    /// The daily puzzle, practice game or challenge being played, which it's saved under.
+    pub(crate) id: GameId,
     pub(crate) save_data: SaveData,
+    pub(crate) latest: NaiveDate,
+    pub(crate) rolled_over: bool,
 }
 
 impl GameManager {
     pub(crate) async fn new() -> anyhow::Result<Self> {
-        let mut game: wordle::Game = wordle::GameInfo::today().await?.into();
-        let save_data = SaveData::from_file().unwrap_or(SaveData::new());
+        GameManager::open(
+            &config().source,
+            SaveData::from_file()?,
+            PuzzleDb::from_file()?,
+        )
+        .await
+    }
 
-        if let Some(saved_game) = save_data.load(game.info.number) {
+    /// Opens the save file on the puzzle for `date`, the practice game of `seed` or the challenge
+    /// with `code`, or else today's.
+    pub(crate) async fn start(
+        date: Option<NaiveDate>,
+        seed: Option<u64>,
+        challenge: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let mut game = GameManager::new().await?;
+        if let Some(date) = date {
+            game.goto(date)
+                .await
+                .with_context(|| format!("Failed to fetch the Wordle for {date}"))?;
+        }
+        if let Some(seed) = seed {
+            game.play_practice(seed);
+        }
+        if let Some(code) = challenge {
+            game.play_challenge(code)
+                .with_context(|| format!("Failed to open challenge {code}"))?;
+        }
+        Ok(game)
+    }
+
+    pub(crate) async fn open(
+        source: &str,
+        save_data: SaveData,
+        puzzles: PuzzleDb,
+    ) -> anyhow::Result<Self> {
+        let mut game: wordle::Game = GameManager::info(source, &puzzles, today())
+            .await
+            .with_context(|| "Failed to fetch today's Wordle")?
+            .into();
+
+        let id = GameId::Daily(game.info.number);
+        if let Some(saved_game) = save_data.load(&id) {
             saved_game.clone_into(&mut game);
         }
+        game.hard_mode = config().hard_mode;
 
         Ok(Self {
             game,
-            date: Utc::now().date_naive(),
+            source: source.to_string(),
+            puzzles,
+            date: today(),
+            id,
             save_data,
+            latest: today(),
+            rolled_over: false,
         })
     }
 
+    /// The puzzle for `date`, from the synced archive if it's there.
+    async fn info(
+        source: &str,
+        puzzles: &PuzzleDb,
+        date: NaiveDate,
+    ) -> Result<wordle::GameInfo, FetchError> {
+        match puzzles.get(date) {
+            Some(info) => Ok(info.clone()),
+            None => wordle::GameInfo::at(source, date).await,
+        }
+    }
+
+    /// Stats of the kind of game being played.
     pub(crate) fn stats(&self) -> Stats {
-        self.save_data.stats()
+        self.save_data.stats(self.id.kind())
+    }
+
+    pub(crate) fn archive(&self) -> Archive {
+        let mut archive = Archive::new(&self.save_data, today());
+        archive.select_date(self.date);
+        archive
     }
 
     pub(crate) fn save(&mut self) {
-        self.save_data.save(&self.game);
+        self.save_data.save(self.id.clone(), &self.game);
     }
 
-    async fn goto(&mut self, date: NaiveDate) {
-        self.date = date;
+    pub(crate) fn is_latest(&self) -> bool {
+        self.id.kind() == GameKind::Daily && self.date == self.latest
+    }
 
-        if let Some(game) = self.save_data.load(date_to_wordle_number(date)) {
-            game.clone_into(&mut self.game);
-        } else if let Ok(game) = wordle::GameInfo::at(self.date)
-            .await
-            .map(|info| -> wordle::Game { info.into() })
-        {
-            game.clone_into(&mut self.game);
+    /// Switches to the game saved as `id`, or a new one of `info`.
+    fn resume(&mut self, id: GameId, info: wordle::GameInfo) {
+        self.game = match self.save_data.load(&id) {
+            Some(game) => game.clone(),
+            None => info.into(),
+        };
+        self.game.hard_mode = config().hard_mode;
+        self.id = id;
+        self.rolled_over = false;
+    }
+
+    pub(crate) fn play_practice(&mut self, seed: u64) {
+        self.resume(GameId::Practice(seed), wordle::GameInfo::seeded(seed));
+    }
+
+    pub(crate) fn play_challenge(&mut self, code: &str) -> Result<(), ChallengeError> {
+        let code = challenge::normalize(code)?;
+        let info = wordle::GameInfo::challenge(&code)?;
+        self.resume(GameId::Challenge(code), info);
+        Ok(())
+    }
+
+    pub(crate) fn check_rollover(&mut self) {
+        if today() > self.latest {
+            self.rolled_over |= self.is_latest();
+            self.latest = today();
         }
     }
 
-    async fn offset_by(&mut self, offset: i32) {
+    pub(crate) async fn goto(&mut self, date: NaiveDate) -> Result<(), FetchError> {
+        let id = GameId::Daily(date_to_wordle_number(date));
+        let saved = self
+            .save_data
+            .load(&id)
+            .filter(|game| game.info.date() == Some(date));
+        self.game = match saved {
+            Some(game) => game.clone(),
+            None => GameManager::info(&self.source, &self.puzzles, date)
+                .await?
+                .into(),
+        };
+        self.game.hard_mode = config().hard_mode;
+
+        self.date = date;
+        self.id = id;
+        // The notice is about the game that was left behind
+        self.rolled_over = false;
+        Ok(())
+    }
+
+    async fn offset_by(&mut self, offset: i32) -> Result<(), FetchError> {
+        self.check_rollover();
         let new_date = self.date + Duration::days(offset as i64);
-        if new_date < FIRST_WORDLE_DATE || new_date > Utc::now().date_naive() {
-            return;
+        if new_date < FIRST_WORDLE_DATE || new_date > self.latest {
+            return Ok(());
         }
-        self.goto(new_date).await;
+        self.goto(new_date).await
     }
 
-    pub(crate) async fn next(&mut self) {
-        self.offset_by(1).await;
+    pub(crate) async fn next(&mut self) -> Result<(), FetchError> {
+        self.offset_by(1).await
     }
 
-    pub(crate) async fn previous(&mut self) {
-        self.offset_by(-1).await;
+    pub(crate) async fn previous(&mut self) -> Result<(), FetchError> {
+        self.offset_by(-1).await
     }
 
-    pub(crate) async fn first(&mut self) {
-        self.goto(FIRST_WORDLE_DATE).await;
+    pub(crate) async fn first(&mut self) -> Result<(), FetchError> {
+        self.goto(FIRST_WORDLE_DATE).await
     }
 
-    pub(crate) async fn last(&mut self) {
-        self.goto(Utc::now().date_naive()).await;
+    pub(crate) async fn last(&mut self) -> Result<(), FetchError> {
+        self.check_rollover();
+        self.goto(self.latest).await
     }
 }
 
@@ -91,3 +231,33 @@ impl DerefMut for GameManager {
         &mut self.game
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{Faults, Server};
+
+    #[tokio::test]
+    async fn leaving_a_rolled_over_game_clears_the_notice() {
+        let source = Server::generated(Faults::default()).spawn().await;
+        let mut game = GameManager::open(&source, SaveData::in_memory(), PuzzleDb::default())
+            .await
+            .unwrap();
+
+        // Pretend the game was opened yesterday and left running past midnight
+        let yesterday = today() - Duration::days(1);
+        game.goto(yesterday).await.unwrap();
+        game.latest = yesterday;
+        game.check_rollover();
+        assert!(game.rolled_over);
+        assert!(!game.is_latest());
+
+        game.previous().await.unwrap();
+        assert_eq!(game.date, yesterday - Duration::days(1));
+        assert!(!game.rolled_over);
+
+        game.next().await.unwrap();
+        assert_eq!(game.date, yesterday);
+        assert!(!game.rolled_over);
+    }
+}
@@ -0,0 +1,186 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use rayon::prelude::*;
+
+use crate::{SaveData, save::GameKind, solver, wordle};
+
+pub(crate) struct Analysis {
+    pub(crate) word: String,
+    /// Answers still possible after this guess, on average.
+    pub(crate) expected: f64,
+    pub(crate) worst: usize,
+    /// Bits of information the guess gives, on average.
+    pub(crate) entropy: f64,
+    /// Average number of green, yellow and gray tiles.
+    pub(crate) tiles: [f64; 3],
+}
+
+impl Analysis {
+    pub(crate) fn new(word: &str, answers: &[&str]) -> Self {
+        let mut counts = [0usize; 243];
+        let mut tiles = [0usize; 3];
+        for answer in answers {
+            let mut pattern = solver::pattern(word, answer);
+            counts[pattern as usize] += 1;
+            for _ in 0..5 {
+                tiles[2 - (pattern % 3) as usize] += 1;
+                pattern /= 3;
+            }
+        }
+
+        let total = answers.len() as f64;
+        let buckets = counts.iter().filter(|&&n| n > 0).map(|&n| n as f64);
+        Self {
+            word: word.to_string(),
+            expected: buckets.clone().map(|n| n * n).sum::<f64>() / total,
+            worst: counts.into_iter().max().unwrap_or_default(),
+            entropy: buckets.map(|n| -n / total * (n / total).log2()).sum(),
+            tiles: tiles.map(|n| n as f64 / total),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Record {
+    pub(crate) games: usize,
+    pub(crate) won: usize,
+    pub(crate) guesses: usize,
+}
+
+impl SaveData {
+    /// How each first guess in a daily puzzle went, keyed by the guess in lowercase.
+    pub(crate) fn openers(&self) -> BTreeMap<String, Record> {
+        let mut openers = BTreeMap::<String, Record>::new();
+
+        for game in self
+            .games_of(GameKind::Daily)
+            .filter(|game| game.has_finished())
+        {
+            let word = game.grid[0]
+                .letters
+                .iter()
+                .map(|l| l.char.to_ascii_lowercase())
+                .collect::<String>();
+            let record = openers.entry(word).or_default();
+            record.games += 1;
+            if let Some(guesses) = game.won_in() {
+                record.won += 1;
+                record.guesses += guesses;
+            }
+        }
+
+        openers
+    }
+}
+
+fn print_header() {
+    println!(
+        "{:<6}  {:>7}  {:>8}  {:>5}  {:>5}  {:>6}  {:>4}",
+        "Opener", "Entropy", "Expected", "Worst", "Green", "Yellow", "Gray"
+    );
+}
+
+fn print_row(analysis: &Analysis) {
+    let [green, yellow, gray] = analysis.tiles;
+    println!(
+        "{:<6}  {:>7.3}  {:>8.2}  {:>5}  {green:>5.2}  {yellow:>6.2}  {gray:>4.2}",
+        analysis.word.to_uppercase(),
+        analysis.entropy,
+        analysis.expected,
+        analysis.worst,
+    );
+}
+
+fn print_record(word: &str, record: &Record) {
+    let games = if record.games == 1 { "game" } else { "games" };
+    print!(
+        "{}: {} {games}, won {}",
+        word.to_uppercase(),
+        record.games,
+        record.won
+    );
+    if record.won > 0 {
+        print!(
+            " in {:.2} guesses on average",
+            record.guesses as f64 / record.won as f64
+        );
+    }
+    println!();
+}
+
+pub(crate) fn run(word: &str) -> anyhow::Result<()> {
+    let word = word.to_lowercase();
+    anyhow::ensure!(wordle::is_allowed(&word), "{word} is not in the word list");
+
+    let answers = wordle::answers().collect::<Vec<_>>();
+    let analysis = Analysis::new(&word, &answers);
+    let [green, yellow, gray] = analysis.tiles;
+
+    println!("{}, against {} answers", word.to_uppercase(), answers.len());
+    println!("Expected answers left: {:.2}", analysis.expected);
+    println!("Worst case:            {}", analysis.worst);
+    println!("Entropy:               {:.3} bits", analysis.entropy);
+    println!("Tiles on average:      {green:.2} green, {yellow:.2} yellow, {gray:.2} gray");
+
+    if let Ok(save_data) = SaveData::read_only() {
+        println!();
+        match save_data.openers().get(&word) {
+            Some(record) => print_record(&word, record),
+            None => println!(
+                "You haven't finished a game opening with {}",
+                word.to_uppercase()
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn run_top(top: usize) -> anyhow::Result<()> {
+    let answers = wordle::answers().collect::<Vec<_>>();
+
+    let mut ranked = wordle::allowed()
+        .collect::<Vec<_>>()
+        .par_iter()
+        .map(|word| Analysis::new(word, &answers))
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.entropy.total_cmp(&a.entropy));
+
+    print_header();
+    for analysis in ranked.iter().take(top) {
+        print_row(analysis);
+    }
+
+    let Ok(save_data) = SaveData::read_only() else {
+        return Ok(());
+    };
+    let mut openers = save_data.openers().into_iter().collect::<Vec<_>>();
+    if openers.is_empty() {
+        return Ok(());
+    }
+    openers.sort_by_key(|(_, record)| Reverse(record.games));
+
+    println!();
+    println!(
+        "{:<6}  {:>5}  {:>4}  {:>7}  {:>7}  {:>8}  {:>5}",
+        "Yours", "Games", "Won", "Average", "Entropy", "Expected", "Rank"
+    );
+    for (word, record) in openers.iter().take(top) {
+        let average = record.guesses as f64 / record.won.max(1) as f64;
+        let Some(rank) = ranked.iter().position(|a| &a.word == word) else {
+            continue;
+        };
+        let analysis = &ranked[rank];
+        println!(
+            "{:<6}  {:>5}  {:>4}  {average:>7.2}  {:>7.3}  {:>8.2}  {:>5}",
+            word.to_uppercase(),
+            record.games,
+            record.won,
+            analysis.entropy,
+            analysis.expected,
+            rank + 1,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use chrono::NaiveDate;
+use crossterm::style::Stylize;
+
+use crate::{manager::GameManager, wordle};
+
+fn format_row(row: &wordle::Row, emoji: bool) -> String {
+    let word = row.letters.iter().map(|l| l.char).collect::<String>();
+
+    if emoji {
+        let squares = row
+            .letters
+            .iter()
+            .map(|l| match l.color {
+                Some(wordle::Color::Green) => '\u{1f7e9}',
+                Some(wordle::Color::Yellow) => '\u{1f7e8}',
+                _ => '\u{2b1b}',
+            })
+            .collect::<String>();
+        return format!("{word} {squares}");
+    }
+
+    row.letters
+        .iter()
+        .map(|l| {
+            let tile = format!(" {} ", l.char).bold();
+            match l.color {
+                Some(wordle::Color::Green) => tile.black().on_green(),
+                Some(wordle::Color::Yellow) => tile.black().on_yellow(),
+                _ => tile.white().on_dark_grey(),
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+pub(crate) async fn run(
+    date: Option<NaiveDate>,
+    seed: Option<u64>,
+    challenge: Option<&str>,
+    emoji: bool,
+) -> anyhow::Result<()> {
+    let emoji = emoji || !io::stdout().is_terminal();
+    let prompt = io::stdin().is_terminal();
+
+    let mut game = GameManager::start(date, seed, challenge).await?;
+
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}", game.info.title())?;
+    for row in &game.grid[..game.index.0] {
+        writeln!(out, "{}", format_row(row, emoji))?;
+    }
+
+    let mut lines = io::stdin().lock().lines();
+    while !game.has_finished() {
+        if prompt {
+            write!(out, "Guess {}/6: ", game.index.0 + 1)?;
+            out.flush()?;
+        }
+
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        let guess = line.trim();
+        if guess.is_empty() {
+            continue;
+        }
+
+        let row = game.index.0;
+        match game.guess(guess) {
+            Ok(()) => writeln!(out, "{}", format_row(&game.grid[row], emoji))?,
+            Err(e) => writeln!(out, "{e}")?,
+        }
+        game.save();
+    }
+
+    if let Some(result) = game.result() {
+        writeln!(out, "{result}")?;
+    }
+
+    Ok(())
+}
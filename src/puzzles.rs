@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    time::Instant,
+};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use futures::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::config,
+    fetch::FetchError,
+    manager::{self, FIRST_WORDLE_DATE, date_to_wordle_number},
+    save,
+    wordle::GameInfo,
+};
+
+const MAGIC: &[u8] = b"WRDP";
+const VERSION: u8 = 1;
+/// How many puzzles to fetch between writes of the database, so an interrupted sync keeps most
+/// of its progress.
+const SAVE_EVERY: usize = 50;
+
+/// Every puzzle synced so far, by number.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct PuzzleDb {
+    puzzles: BTreeMap<u32, GameInfo>,
+}
+
+impl PuzzleDb {
+    fn path() -> anyhow::Result<PathBuf> {
+        Ok(save::data_dir()?.join("puzzles.dat"))
+    }
+
+    /// Loads the local puzzle database, or an empty one if nothing has been synced yet.
+    pub(crate) fn from_file() -> anyhow::Result<Self> {
+        let path = PuzzleDb::path()?;
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(PuzzleDb::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        PuzzleDb::from_bytes(&bytes).with_context(|| format!("Failed to load {}", path.display()))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let versioned = bytes
+            .strip_prefix(MAGIC)
+            .with_context(|| "Not a puzzle database")?;
+        match versioned.split_first() {
+            Some((&VERSION, data)) => {
+                postcard::from_bytes(data).with_context(|| "Failed to decode")
+            }
+            Some((version, _)) => anyhow::bail!(
+                "Puzzle database version {version} isn't supported by this version of wordle, \
+                which writes version {VERSION}"
+            ),
+            None => anyhow::bail!("Puzzle database is truncated"),
+        }
+    }
+
+    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        Ok(postcard::to_extend(self, bytes)?)
+    }
+
+    fn save_to_file(&self) -> anyhow::Result<()> {
+        let path = PuzzleDb::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&path, self.to_bytes()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub(crate) fn get(&self, date: NaiveDate) -> Option<&GameInfo> {
+        if date < FIRST_WORDLE_DATE {
+            return None;
+        }
+        self.puzzles
+            .get(&date_to_wordle_number(date))
+            .filter(|info| info.date() == Some(date))
+    }
+
+    fn insert(&mut self, info: GameInfo) {
+        self.puzzles.insert(info.number, info);
+    }
+
+    /// Dates from the first Wordle up to `until` that haven't been synced yet.
+    fn missing(&self, until: NaiveDate) -> Vec<NaiveDate> {
+        FIRST_WORDLE_DATE
+            .iter_days()
+            .take_while(|&date| date <= until)
+            .filter(|&date| self.get(date).is_none())
+            .collect()
+    }
+}
+
+/// How a sync went.
+#[derive(Debug, Default)]
+struct Report {
+    fetched: usize,
+    failed: Vec<(NaiveDate, FetchError)>,
+    rate_limited: bool,
+}
+
+/// Fetches every missing puzzle up to `until`, `concurrency` at a time. `progress` is called with
+/// the number of puzzles done so far and the number there were to do.
+async fn sync(
+    db: &mut PuzzleDb,
+    source: &str,
+    until: NaiveDate,
+    concurrency: usize,
+    mut progress: impl FnMut(&PuzzleDb, usize, usize) -> anyhow::Result<()>,
+) -> anyhow::Result<Report> {
+    let missing = db.missing(until);
+    let total = missing.len();
+    let mut report = Report::default();
+
+    let mut fetches = stream::iter(missing)
+        .map(|date| async move { (date, GameInfo::at(source, date).await) })
+        .buffer_unordered(concurrency.max(1));
+    let mut done = 0;
+    while let Some((date, result)) = fetches.next().await {
+        done += 1;
+        match result {
+            Ok(info) => {
+                db.insert(info);
+                report.fetched += 1;
+            }
+            // The fetcher has already backed off and retried, so leave the rest for next time.
+            Err(FetchError::RateLimited) => {
+                report.rate_limited = true;
+                break;
+            }
+            Err(e) => report.failed.push((date, e)),
+        }
+        progress(db, done, total)?;
+    }
+
+    report.failed.sort_by_key(|&(date, _)| date);
+    Ok(report)
+}
+
+pub(crate) async fn run(concurrency: usize) -> anyhow::Result<()> {
+    let mut db = PuzzleDb::from_file()?;
+    let interactive = io::stderr().is_terminal();
+    let start = Instant::now();
+
+    let report = sync(
+        &mut db,
+        &config().source,
+        manager::today(),
+        concurrency,
+        |db, done, total| {
+            if interactive {
+                eprint!("\rSyncing puzzles: {done}/{total}");
+                let _ = io::stderr().flush();
+            }
+            if done % SAVE_EVERY == 0 {
+                db.save_to_file()?;
+            }
+            Ok(())
+        },
+    )
+    .await;
+    if interactive {
+        eprintln!();
+    }
+    db.save_to_file()?;
+    let report = report?;
+
+    println!(
+        "Fetched {} puzzles in {:.1?}, {} synced in total",
+        report.fetched,
+        start.elapsed(),
+        db.len()
+    );
+    for (date, e) in &report.failed {
+        println!("Failed to fetch the Wordle for {date}: {e}");
+    }
+    if report.rate_limited {
+        println!("Stopped early after being rate limited. Run it again later to pick up from here");
+    }
+    if !report.failed.is_empty() || report.rate_limited {
+        anyhow::bail!("Sync is incomplete");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Days;
+
+    use super::*;
+    use crate::{
+        SaveData,
+        manager::GameManager,
+        server::{Faults, Server},
+        wordle::{Answer, Puzzle},
+    };
+
+    fn until() -> NaiveDate {
+        FIRST_WORDLE_DATE + Days::new(29)
+    }
+
+    #[tokio::test]
+    async fn syncs_and_resumes() {
+        let source = Server::generated(Faults::default()).spawn().await;
+        let mut db = PuzzleDb::default();
+
+        let report = sync(
+            &mut db,
+            &source,
+            FIRST_WORDLE_DATE + Days::new(9),
+            4,
+            |_, _, _| Ok(()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.fetched, 10);
+        assert_eq!(db.len(), 10);
+
+        let mut calls = Vec::new();
+        let report = sync(&mut db, &source, until(), 4, |_, done, total| {
+            calls.push((done, total));
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(report.fetched, 20);
+        assert!(report.failed.is_empty());
+        assert_eq!(calls.last(), Some(&(20, 20)));
+        assert_eq!(db.len(), 30);
+        assert!(db.missing(until()).is_empty());
+
+        let info = db.get(until()).unwrap();
+        assert_eq!(info.number, 29);
+        assert_eq!(info.word, GameInfo::seeded(29).word);
+    }
+
+    #[tokio::test]
+    async fn reports_puzzles_that_fail_verification() {
+        let puzzles = FIRST_WORDLE_DATE
+            .iter_days()
+            .take(3)
+            .map(|date| Puzzle {
+                id: 0,
+                solution: "crane".to_string(),
+                print_date: date,
+                days_since_launch: Some(date_to_wordle_number(date)),
+                editor: None,
+            })
+            .enumerate()
+            .map(|(i, mut puzzle)| {
+                // The second puzzle claims to be the first one.
+                if i == 1 {
+                    puzzle.days_since_launch = Some(0);
+                }
+                puzzle
+            })
+            .collect();
+        let source = Server::from_puzzles(puzzles, Faults::default())
+            .spawn()
+            .await;
+        let mut db = PuzzleDb::default();
+
+        let until = FIRST_WORDLE_DATE + Days::new(3);
+        let report = sync(&mut db, &source, until, 2, |_, _, _| Ok(()))
+            .await
+            .unwrap();
+        assert_eq!(report.fetched, 2);
+        assert_eq!(db.len(), 2);
+        let failed = report
+            .failed
+            .iter()
+            .map(|(date, e)| (*date, matches!(e, FetchError::NotFound)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failed,
+            [(FIRST_WORDLE_DATE + Days::new(1), false), (until, true)]
+        );
+        assert!(db.get(FIRST_WORDLE_DATE + Days::new(1)).is_none());
+    }
+
+    #[tokio::test]
+    async fn game_manager_plays_synced_puzzles_offline() {
+        let today = manager::today();
+        let yesterday = today - Days::new(1);
+        let source = Server::generated(Faults::default()).spawn().await;
+        let mut db = PuzzleDb::default();
+        for date in [yesterday, today] {
+            db.insert(GameInfo::at(&source, date).await.unwrap());
+        }
+
+        let unreachable = "http://127.0.0.1:9/svc/wordle/v2";
+        let mut game = GameManager::open(unreachable, SaveData::in_memory(), db)
+            .await
+            .unwrap();
+        assert_eq!(
+            game.info.word,
+            GameInfo::seeded(date_to_wordle_number(today).into()).word
+        );
+        game.previous().await.unwrap();
+        assert_eq!(game.date, yesterday);
+        assert!(game.first().await.is_err());
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut db = PuzzleDb::default();
+        db.insert(GameInfo {
+            id: 1,
+            number: 0,
+            word: Answer::new("cigar"),
+            date_string: "2021-06-19".to_string(),
+            editor: None,
+        });
+
+        let db = PuzzleDb::from_bytes(&db.to_bytes().unwrap()).unwrap();
+        assert_eq!(db.get(FIRST_WORDLE_DATE).unwrap().word.reveal(), "cigar");
+        assert!(PuzzleDb::from_bytes(b"WRDP\x09").is_err());
+        assert!(PuzzleDb::from_bytes(b"nonsense").is_err());
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Context;
+use crossterm::event::{Event, EventStream};
+use futures::StreamExt;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader, Lines},
+    net::{
+        TcpListener, TcpStream, ToSocketAddrs,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::broadcast::{self, error::RecvError},
+    time::{self, MissedTickBehavior},
+};
+
+use crate::{
+    Message, RunningState, TICK_RATE,
+    animation::Animation,
+    config::config,
+    manager,
+    widget::{self, Board},
+    wordle::{Color, Game, GameInfo},
+};
+
+/// Sent by players to the host, one JSON object per line.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Join {
+        name: String,
+    },
+    /// The colors of every row submitted so far. Letters are never sent.
+    Progress {
+        rows: Vec<[Color; 5]>,
+    },
+}
+
+/// Sent by the host to players, one JSON object per line.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Update {
+    Start { you: usize, info: GameInfo },
+    Standings { players: Vec<Player> },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) struct Player {
+    pub(crate) id: usize,
+    pub(crate) name: String,
+    pub(crate) rows: Vec<[Color; 5]>,
+    /// Milliseconds from the start of the race to finishing.
+    pub(crate) time: Option<u64>,
+    pub(crate) connected: bool,
+}
+
+impl Player {
+    pub(crate) fn new(id: usize, name: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            rows: Vec::new(),
+            time: None,
+            connected: true,
+        }
+    }
+
+    pub(crate) fn solved_in(&self) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| *row == [Color::Green; 5])
+            .map(|i| i + 1)
+    }
+
+    pub(crate) fn has_finished(&self) -> bool {
+        self.solved_in().is_some() || self.rows.len() >= 6
+    }
+
+    fn is_done(&self) -> bool {
+        self.has_finished() || !self.connected
+    }
+}
+
+/// Players from first to last: solvers in the order they solved it, with ties going to fewer
+/// guesses, then everyone else.
+pub(crate) fn ranked(players: &[Player]) -> Vec<&Player> {
+    let mut ranked = players.iter().collect::<Vec<_>>();
+    ranked.sort_by_key(|player| match player.solved_in() {
+        Some(guesses) => (false, player.time.unwrap_or(u64::MAX), guesses),
+        None => (true, u64::MAX, usize::MAX),
+    });
+    ranked
+}
+
+/// Whether everyone still connected has finished.
+pub(crate) fn is_over(players: &[Player]) -> bool {
+    players.iter().all(Player::is_done)
+}
+
+/// The first to solve it, as soon as anyone has.
+pub(crate) fn winner(players: &[Player]) -> Option<&Player> {
+    ranked(players)
+        .into_iter()
+        .next()
+        .filter(|player| player.solved_in().is_some())
+}
+
+async fn write_line(
+    writer: &mut (impl AsyncWrite + Unpin),
+    value: &impl Serialize,
+) -> io::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    writer.write_all(&line).await
+}
+
+/// Keeps everyone's progress and sends the standings to every player when it changes.
+struct Hub {
+    info: GameInfo,
+    /// Everyone's time is counted from here, so times can be compared.
+    started: Instant,
+    players: Mutex<Vec<Player>>,
+    standings: broadcast::Sender<Vec<Player>>,
+}
+
+impl Hub {
+    fn update<T>(&self, f: impl FnOnce(&mut Vec<Player>) -> T) -> T {
+        let mut players = self.players.lock().unwrap();
+        let result = f(&mut players);
+        let _ = self.standings.send(players.clone());
+        result
+    }
+
+    fn progress(&self, id: usize, rows: Vec<[Color; 5]>) {
+        self.update(|players| {
+            let player = &mut players[id];
+            if player.has_finished() || rows.len() < player.rows.len() {
+                return;
+            }
+            player.rows = rows.into_iter().take(6).collect();
+            if player.has_finished() {
+                player.time = Some(self.started.elapsed().as_millis() as u64);
+            }
+        });
+    }
+
+    async fn connect(&self, stream: TcpStream) -> anyhow::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        let Some(line) = lines.next_line().await? else {
+            return Ok(());
+        };
+        let Request::Join { name } = serde_json::from_str(&line)? else {
+            anyhow::bail!("Expected to join before anything else");
+        };
+
+        // Subscribe first so this player hears about their own arrival
+        let mut standings = self.standings.subscribe();
+        let id = self.update(|players| {
+            let id = players.len();
+            players.push(Player::new(id, &name));
+            id
+        });
+
+        let result = async {
+            let start = Update::Start {
+                you: id,
+                info: self.info.clone(),
+            };
+            write_line(&mut writer, &start).await?;
+
+            loop {
+                tokio::select! {
+                    line = lines.next_line() => match line? {
+                        Some(line) => match serde_json::from_str(&line)? {
+                            Request::Progress { rows } => self.progress(id, rows),
+                            Request::Join { .. } => {}
+                        },
+                        None => return Ok(()),
+                    },
+                    players = standings.recv() => match players {
+                        Ok(players) => write_line(&mut writer, &Update::Standings { players }).await?,
+                        // Each update has everyone's progress, so only the latest matters
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => return Ok(()),
+                    },
+                }
+            }
+        }
+        .await;
+
+        self.update(|players| players[id].connected = false);
+        result
+    }
+}
+
+/// Hosts a race on `listener` until the process exits. Everyone who joins plays `info`.
+pub(crate) async fn serve(listener: TcpListener, info: GameInfo) -> anyhow::Result<()> {
+    let hub = Arc::new(Hub {
+        info,
+        started: Instant::now(),
+        players: Mutex::new(Vec::new()),
+        standings: broadcast::channel(64).0,
+    });
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let hub = hub.clone();
+        tokio::spawn(async move {
+            let _ = hub.connect(stream).await;
+        });
+    }
+}
+
+/// A player's connection to the host.
+pub(crate) struct Connection {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    /// Joins the race at `addr`, returning the player's id and the puzzle everyone is playing.
+    pub(crate) async fn join(
+        addr: impl ToSocketAddrs,
+        name: &str,
+    ) -> anyhow::Result<(Self, usize, GameInfo)> {
+        let (reader, mut writer) = TcpStream::connect(addr).await?.into_split();
+        let join = Request::Join {
+            name: name.to_string(),
+        };
+        write_line(&mut writer, &join).await?;
+
+        let mut connection = Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+        };
+        match connection.next().await? {
+            Some(Update::Start { you, info }) => Ok((connection, you, info)),
+            Some(Update::Standings { .. }) => anyhow::bail!("The host didn't start the race"),
+            None => anyhow::bail!("The host closed the connection"),
+        }
+    }
+
+    async fn next(&mut self) -> anyhow::Result<Option<Update>> {
+        match self.lines.next_line().await? {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn send_progress(&mut self, game: &Game) -> anyhow::Result<()> {
+        let rows = game.grid[..game.index.0]
+            .iter()
+            .map(|row| row.letters.map(|l| l.color.unwrap_or_default()))
+            .collect();
+        write_line(&mut self.writer, &Request::Progress { rows }).await?;
+        Ok(())
+    }
+
+    /// Waits for the next standings, or `None` once the host has gone.
+    pub(crate) async fn standings(&mut self) -> anyhow::Result<Option<Vec<Player>>> {
+        loop {
+            match self.next().await? {
+                Some(Update::Standings { players }) => return Ok(Some(players)),
+                Some(Update::Start { .. }) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+struct Race {
+    game: Game,
+    you: usize,
+    players: Vec<Player>,
+    connection: Option<Connection>,
+    /// Shown until anyone else joins.
+    waiting: Option<String>,
+    notice: Option<String>,
+    animations: Vec<Animation>,
+    running_state: RunningState,
+}
+
+impl Race {
+    fn status(&self) -> Option<String> {
+        if self.connection.is_none() {
+            return Some("Lost connection to the host".to_string());
+        }
+        if !self.animations.is_empty() {
+            return None;
+        }
+        if let Some(player) = winner(&self.players) {
+            return Some(if player.id == self.you {
+                "You win!".to_string()
+            } else {
+                format!("{} wins", player.name)
+            });
+        }
+        if !self.game.has_finished() {
+            return None;
+        }
+        if !is_over(&self.players) {
+            return Some("Waiting for the others to finish".to_string());
+        }
+        Some("Nobody solved it".to_string())
+    }
+
+    async fn update(&mut self, msg: Message) {
+        if let Message::Tick = msg {
+            self.animations.retain(|a| !a.is_done());
+            return;
+        }
+        self.notice = None;
+
+        match msg {
+            Message::Letter(char) => crate::add_char(&mut self.game, &mut self.animations, char),
+            Message::Backspace => self.game.backspace(),
+            Message::Submit => {
+                let row = self.game.index.0;
+                self.notice = crate::submit(&mut self.game, &mut self.animations);
+                if self.game.index.0 > row
+                    && let Some(connection) = &mut self.connection
+                    && connection.send_progress(&self.game).await.is_err()
+                {
+                    self.connection = None;
+                }
+            }
+            Message::Quit => self.running_state = RunningState::Done,
+            _ => {}
+        }
+    }
+
+    fn view(&self, frame: &mut Frame) {
+        let opponents = self
+            .players
+            .iter()
+            .filter(|player| player.id != self.you)
+            .collect::<Vec<_>>();
+
+        let area = frame.area();
+        let [board_area, side_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(widget::MINI_GRID_WIDTH),
+        ])
+        .areas(area);
+        let (board_area, side_area) = if opponents.is_empty() || !Board::fits(board_area) {
+            (area, None)
+        } else {
+            (board_area, Some(side_area))
+        };
+
+        let status = self.status();
+        let waiting = self.waiting.as_deref().filter(|_| opponents.is_empty());
+        let board = Board {
+            game: &self.game,
+            notice: self.notice.as_deref().or(status.as_deref()).or(waiting),
+            animations: &self.animations,
+            countdown: None,
+        };
+        frame.render_widget(board, board_area);
+
+        let Some(side_area) = side_area else {
+            return;
+        };
+        let fitting = (side_area.height / widget::MINI_GRID_HEIGHT) as usize;
+        let areas = Layout::vertical(vec![
+            Constraint::Length(widget::MINI_GRID_HEIGHT);
+            fitting.min(opponents.len())
+        ])
+        .flex(Flex::Start)
+        .split(side_area);
+        for (player, area) in opponents.into_iter().zip(areas.iter()) {
+            frame.render_widget(player, *area);
+        }
+    }
+}
+
+/// Joins the race at `addr` and plays it full screen.
+async fn play(addr: impl ToSocketAddrs, name: &str, waiting: Option<String>) -> anyhow::Result<()> {
+    let (connection, you, info) = Connection::join(addr, name).await?;
+    let mut game = Game::from(info);
+    game.hard_mode = config().hard_mode;
+    let mut race = Race {
+        game,
+        you,
+        players: Vec::new(),
+        connection: Some(connection),
+        waiting,
+        notice: None,
+        animations: Vec::new(),
+        running_state: RunningState::Running,
+    };
+
+    let mut terminal = ratatui::init();
+    let mut events = EventStream::new();
+    let mut ticks = time::interval(TICK_RATE);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    while race.running_state == RunningState::Running {
+        terminal
+            .draw(|f| race.view(f))
+            .expect("failed to draw frame");
+
+        let message = tokio::select! {
+            _ = ticks.tick(), if !race.animations.is_empty() => Some(Message::Tick),
+            event = events.next() => match event {
+                Some(event) => match event.expect("failed to read event") {
+                    Event::Key(e) if e.is_press() => {
+                        config().keys.message(&e, !race.game.has_finished())
+                    }
+                    _ => None,
+                },
+                None => Some(Message::Quit),
+            },
+            standings = async { race.connection.as_mut()?.standings().await.ok().flatten() },
+                if race.connection.is_some() =>
+            {
+                match standings {
+                    Some(players) => race.players = players,
+                    None => race.connection = None,
+                }
+                None
+            }
+        };
+
+        if let Some(message) = message {
+            race.update(message).await;
+        }
+    }
+    ratatui::restore();
+
+    Ok(())
+}
+
+/// Hosts a race on `port` of every interface and plays in it. Without a seed, the race is on
+/// today's puzzle.
+pub(crate) async fn host(port: u16, seed: Option<u64>, name: &str) -> anyhow::Result<()> {
+    let info = match seed {
+        Some(seed) => GameInfo::seeded(seed),
+        None => GameInfo::at(&config().source, manager::today())
+            .await
+            .with_context(|| "Failed to fetch today's Wordle")?,
+    };
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .await
+        .with_context(|| format!("Failed to listen on port {port}"))?;
+    let port = listener.local_addr()?.port();
+    tokio::spawn(serve(listener, info));
+
+    play(
+        ("127.0.0.1", port),
+        name,
+        Some(format!("Waiting for players on port {port}")),
+    )
+    .await
+    .with_context(|| "Failed to join your own race")
+}
+
+pub(crate) async fn join(addr: &str, name: &str) -> anyhow::Result<()> {
+    play(addr, name, None)
+        .await
+        .with_context(|| format!("Failed to join the race at {addr}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::wordle::Answer;
+
+    async fn start() -> String {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let info = GameInfo {
+            id: 0,
+            number: 1000,
+            word: Answer::new("crane"),
+            date_string: "2024-03-15".to_string(),
+            editor: None,
+        };
+        tokio::spawn(serve(listener, info));
+        addr
+    }
+
+    async fn join(addr: &str, name: &str) -> (Connection, Game) {
+        let (connection, _, info) = Connection::join(addr, name).await.unwrap();
+        (connection, Game::from(info))
+    }
+
+    async fn guess(connection: &mut Connection, game: &mut Game, word: &str) {
+        game.guess(word).unwrap();
+        connection.send_progress(game).await.unwrap();
+    }
+
+    /// Reads standings until they satisfy `done`.
+    async fn until(connection: &mut Connection, done: impl Fn(&[Player]) -> bool) -> Vec<Player> {
+        time::timeout(Duration::from_secs(5), async {
+            loop {
+                let players = connection.standings().await.unwrap().unwrap();
+                if done(&players) {
+                    return players;
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for standings")
+    }
+
+    #[tokio::test]
+    async fn races_over_loopback() {
+        let addr = start().await;
+        let (mut alice, mut alice_game) = join(&addr, "alice").await;
+        let (mut bob, mut bob_game) = join(&addr, "bob").await;
+        let (mut carol, mut carol_game) = join(&addr, "carol").await;
+        assert_eq!(alice_game.info.word, carol_game.info.word);
+        until(&mut alice, |players| players.len() == 3).await;
+
+        // Alice solves first, so she wins even though Bob then solves in fewer guesses
+        guess(&mut alice, &mut alice_game, "trace").await;
+        guess(&mut alice, &mut alice_game, "crane").await;
+        let players = until(&mut bob, |players| players[0].has_finished()).await;
+        assert_eq!(players[0].solved_in(), Some(2));
+        assert_eq!(players[0].rows[0], score_of("trace"));
+        assert_eq!(winner(&players).unwrap().name, "alice");
+        assert!(!is_over(&players));
+
+        time::sleep(Duration::from_millis(5)).await;
+        guess(&mut bob, &mut bob_game, "crane").await;
+        for word in ["slate", "pious", "dumpy", "fight", "wreck", "zonal"] {
+            guess(&mut carol, &mut carol_game, word).await;
+        }
+
+        let players = until(&mut alice, is_over).await;
+        let names = ranked(&players)
+            .iter()
+            .map(|player| player.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+        assert_eq!(winner(&players).unwrap().name, "alice");
+        assert!(players[0].time < players[1].time);
+        assert_eq!(players[2].solved_in(), None);
+    }
+
+    #[tokio::test]
+    async fn leaving_players_dont_hold_up_the_race() {
+        let addr = start().await;
+        let (mut alice, mut alice_game) = join(&addr, "alice").await;
+        let (bob, _) = join(&addr, "bob").await;
+        until(&mut alice, |players| players.len() == 2).await;
+
+        guess(&mut alice, &mut alice_game, "slate").await;
+        let players = until(&mut alice, |players| players[0].rows.len() == 1).await;
+        assert!(!is_over(&players));
+
+        drop(bob);
+        let players = until(&mut alice, |players| !players[1].connected).await;
+        assert!(!is_over(&players));
+        for word in ["pious", "dumpy", "fight", "wreck", "zonal"] {
+            guess(&mut alice, &mut alice_game, word).await;
+        }
+        let players = until(&mut alice, is_over).await;
+        assert!(winner(&players).is_none());
+    }
+
+    #[test]
+    fn ranks_by_solve_order_then_guesses() {
+        let player = |id: usize, guesses: usize, time: u64| Player {
+            rows: [vec![[Color::Gray; 5]; guesses - 1], vec![[Color::Green; 5]]].concat(),
+            time: Some(time),
+            ..Player::new(id, "")
+        };
+        let failed = Player {
+            rows: vec![[Color::Gray; 5]; 6],
+            time: Some(1000),
+            ..Player::new(4, "")
+        };
+        let players = [
+            player(0, 3, 9000),
+            player(1, 4, 4000),
+            player(2, 2, 20000),
+            player(3, 3, 4000),
+            failed,
+        ];
+
+        let ids = ranked(&players)
+            .iter()
+            .map(|player| player.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [3, 1, 0, 2, 4]);
+        assert_eq!(winner(&players).unwrap().id, 3);
+    }
+
+    fn score_of(guess: &str) -> [Color; 5] {
+        crate::wordle::score(guess, "crane")
+    }
+}
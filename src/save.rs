@@ -1,4 +1,9 @@
-use std::{collections::HashMap, fs, path::PathBuf, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
 
 use anyhow::Context;
 use directories::ProjectDirs;
@@ -6,61 +11,322 @@ use serde::{Deserialize, Serialize};
 
 use crate::wordle;
 
-static SAVE_PATH: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+static DATA_DIR: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
     ProjectDirs::from("dev", "joee", "wordle").map(|dirs| dirs.data_dir().to_path_buf())
 });
 
+/// Where the save file and other local data live.
+pub(crate) fn data_dir() -> anyhow::Result<&'static Path> {
+    DATA_DIR
+        .as_deref()
+        .with_context(|| "Failed to find save directory")
+}
+
+/// Written before the version byte, which older save files didn't have.
+const MAGIC: &[u8] = b"WRDL";
+const VERSION: u8 = 1;
+
+/// Which game a save is for.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub(crate) enum GameId {
+    /// The daily puzzle with this number.
+    Daily(u32),
+    /// A secret answer picked from this seed.
+    Practice(u64),
+    /// A challenge code, formatted by `challenge::normalize`.
+    Challenge(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum GameKind {
+    Daily,
+    Practice,
+    Challenge,
+}
+
+impl GameId {
+    pub(crate) fn kind(&self) -> GameKind {
+        match self {
+            GameId::Daily(_) => GameKind::Daily,
+            GameId::Practice(_) => GameKind::Practice,
+            GameId::Challenge(_) => GameKind::Challenge,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct SaveData {
-    map: HashMap<u32, wordle::Game>,
+    map: HashMap<GameId, wordle::Game>,
+    /// Never written back to the save file. Only `from_file` makes save data that is.
+    #[serde(skip)]
+    in_memory: bool,
 }
 
 impl SaveData {
-    pub(crate) fn new() -> Self {
+    /// Save data that's never written to the save file.
+    pub(crate) fn in_memory() -> Self {
         Self {
             map: HashMap::new(),
+            in_memory: true,
         }
     }
 
+    fn path() -> anyhow::Result<PathBuf> {
+        Ok(data_dir()?.join("save.dat"))
+    }
+
+    /// Loads the save file, or starts afresh if there isn't one yet.
     pub(crate) fn from_file() -> anyhow::Result<Self> {
-        let path = SAVE_PATH
-            .as_ref()
-            .with_context(|| "Failed to find save directory")?
-            .join("save.dat");
-        let file = fs::File::open(path).with_context(|| "Failed to open file")?;
-        let (result, ..) =
-            postcard::from_io((file, &mut [0; 2048])).with_context(|| "Failed to decode")?;
-        Ok(result)
+        let mut save_data = SaveData::read_only()?;
+        save_data.in_memory = false;
+        Ok(save_data)
     }
 
-    fn save_to_file(&self) -> anyhow::Result<()> {
-        let path = SAVE_PATH
-            .as_ref()
-            .with_context(|| "Failed to find save directory")?;
+    /// Loads the save file without ever writing it back. It's empty if there isn't one yet.
+    pub(crate) fn read_only() -> anyhow::Result<Self> {
+        let path = SaveData::path()?;
+        match fs::read(&path) {
+            Ok(bytes) => SaveData::from_bytes(&bytes)
+                .with_context(|| format!("Failed to load {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SaveData::in_memory()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
 
-        let _ = fs::create_dir_all(path);
-        let file = fs::File::create(path.join("save.dat"))?;
+    /// Loads someone else's save file, e.g. one made with `wordle export`. It's never written to.
+    pub(crate) fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        SaveData::from_bytes(&bytes).with_context(|| format!("Failed to load {}", path.display()))
+    }
+
+    pub(crate) fn export(&self, path: &Path) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.map.is_empty(), "No save file to export");
+        fs::write(path, self.to_bytes()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let Some(versioned) = bytes.strip_prefix(MAGIC) else {
+            let save_data: v0::SaveData =
+                postcard::from_bytes(bytes).with_context(|| "Failed to decode")?;
+            return Ok(save_data.into());
+        };
+
+        match versioned.split_first() {
+            Some((&VERSION, data)) => {
+                let mut save_data: SaveData =
+                    postcard::from_bytes(data).with_context(|| "Failed to decode")?;
+                save_data.in_memory = true;
+                Ok(save_data)
+            }
+            Some((version, _)) => anyhow::bail!(
+                "Save file version {version} isn't supported by this version of wordle, \
+                which writes version {VERSION}"
+            ),
+            None => anyhow::bail!("Save file is truncated"),
+        }
+    }
+
+    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        Ok(postcard::to_extend(self, bytes)?)
+    }
 
-        postcard::to_io(self, file)?;
+    fn save_to_file(&self) -> anyhow::Result<()> {
+        let path = SaveData::path()?;
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        fs::write(path, self.to_bytes()?)?;
 
         Ok(())
     }
 
-    pub(crate) fn games(&self) -> impl Iterator<Item = &wordle::Game> {
-        self.map.values()
+    pub(crate) fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub(crate) fn games_of(&self, kind: GameKind) -> impl Iterator<Item = &wordle::Game> {
+        self.map
+            .iter()
+            .filter(move |(id, _)| id.kind() == kind)
+            .map(|(_, game)| game)
     }
 
-    pub(crate) fn save(&mut self, game: &wordle::Game) {
-        self.map.insert(game.info.number, game.clone());
+    pub(crate) fn save(&mut self, id: GameId, game: &wordle::Game) {
+        self.map.insert(id, game.clone());
     }
 
-    pub(crate) fn load(&self, number: u32) -> Option<&wordle::Game> {
-        self.map.get(&number)
+    pub(crate) fn load(&self, id: &GameId) -> Option<&wordle::Game> {
+        self.map.get(id)
     }
 }
 
 impl Drop for SaveData {
     fn drop(&mut self) {
+        if self.in_memory {
+            return;
+        }
         let _ = self.save_to_file();
     }
 }
+
+/// The save format from before it was versioned, kept so old saves still load.
+mod v0 {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::wordle;
+
+    #[derive(Deserialize, Serialize)]
+    pub(super) struct SaveData {
+        pub(super) map: HashMap<u32, Game>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub(super) struct Game {
+        pub(super) grid: [wordle::Row; 6],
+        pub(super) index: (usize, usize),
+        pub(super) info: GameInfo,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub(super) struct GameInfo {
+        pub(super) number: u32,
+        pub(super) word: String,
+        pub(super) date_string: String,
+    }
+
+    impl From<SaveData> for super::SaveData {
+        fn from(save_data: SaveData) -> Self {
+            let mut new = super::SaveData::in_memory();
+            for game in save_data.map.into_values() {
+                let mut info = wordle::GameInfo {
+                    id: 0,
+                    number: game.info.number,
+                    word: wordle::Answer::new(&game.info.word),
+                    date_string: game.info.date_string,
+                    editor: None,
+                };
+                // Numbers defaulted to 0 when a source left out days_since_launch
+                if let Some(date) = info.date() {
+                    info.number = crate::manager::date_to_wordle_number(date);
+                }
+
+                let mut migrated = wordle::Game::from(info);
+                migrated.grid = game.grid;
+                migrated.index = game.index;
+                new.save(super::GameId::Daily(migrated.info.number), &migrated);
+            }
+            new
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_bytes() -> Vec<u8> {
+        let mut game = wordle::Game::from(wordle::GameInfo::seeded(0));
+        game.info.word = wordle::Answer::new("crane");
+        game.guess("trace").unwrap();
+
+        let legacy = v0::SaveData {
+            map: HashMap::from([(
+                0,
+                v0::Game {
+                    grid: game.grid,
+                    index: game.index,
+                    info: v0::GameInfo {
+                        number: 0,
+                        word: "crane".to_string(),
+                        date_string: "2024-03-15".to_string(),
+                    },
+                },
+            )]),
+        };
+        postcard::to_allocvec(&legacy).unwrap()
+    }
+
+    #[test]
+    fn migrates_unversioned_saves() {
+        let save_data = SaveData::from_bytes(&legacy_bytes()).unwrap();
+
+        assert!(save_data.load(&GameId::Daily(0)).is_none());
+        let game = save_data.load(&GameId::Daily(1000)).unwrap();
+        assert_eq!(game.info.number, 1000);
+        assert_eq!(game.info.word.reveal(), "crane");
+        assert_eq!(game.info.editor, None);
+        assert_eq!(game.index, (1, 0));
+        assert_eq!(
+            game.grid[0].letters.map(|l| l.char),
+            ['T', 'R', 'A', 'C', 'E']
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let save_data = SaveData::from_bytes(&legacy_bytes()).unwrap();
+        let bytes = save_data.to_bytes().unwrap();
+        assert!(bytes.starts_with(MAGIC));
+
+        assert!(!bytes.windows(5).any(|window| window == b"crane"));
+
+        let loaded = SaveData::from_bytes(&bytes).unwrap();
+        // Decoded save data is only written back if it came from `from_file`
+        assert!(loaded.in_memory);
+        assert_eq!(
+            loaded
+                .load(&GameId::Daily(1000))
+                .unwrap()
+                .info
+                .word
+                .reveal(),
+            "crane"
+        );
+    }
+
+    #[test]
+    fn keeps_times() {
+        let mut game = wordle::Game::from(wordle::GameInfo::seeded(0));
+        game.info.number = 1000;
+        game.info.word = wordle::Answer::new("crane");
+        game.guess("crane").unwrap();
+        let mut save_data = SaveData::in_memory();
+        save_data.save(GameId::Daily(1000), &game);
+
+        let loaded = SaveData::from_bytes(&save_data.to_bytes().unwrap()).unwrap();
+        let loaded = loaded.load(&GameId::Daily(1000)).unwrap();
+        assert_eq!(loaded.started, game.started);
+        assert!(loaded.time().is_some());
+    }
+
+    #[test]
+    fn keeps_games_apart() {
+        let game = wordle::Game::from(wordle::GameInfo::seeded(0));
+        let mut save_data = SaveData::in_memory();
+        save_data.save(GameId::Daily(0), &game);
+        save_data.save(GameId::Practice(0), &game);
+        save_data.save(GameId::Challenge("ZX5XQ".to_string()), &game);
+
+        let loaded = SaveData::from_bytes(&save_data.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.games_of(GameKind::Daily).count(), 1);
+        assert_eq!(loaded.games_of(GameKind::Practice).count(), 1);
+        assert!(
+            loaded
+                .load(&GameId::Challenge("ZX5XQ".to_string()))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut bytes = SaveData::in_memory().to_bytes().unwrap();
+        bytes[MAGIC.len()] = VERSION + 1;
+        assert!(SaveData::from_bytes(&bytes).is_err());
+        assert!(SaveData::from_bytes(MAGIC).is_err());
+    }
+}
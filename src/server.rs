@@ -0,0 +1,288 @@
+use std::{collections::HashMap, fs, path::Path, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    time,
+};
+
+use crate::{
+    manager::{self, FIRST_WORDLE_DATE, date_to_wordle_number},
+    wordle::{self, Puzzle},
+};
+
+pub(crate) const PATH: &str = "/svc/wordle/v2";
+
+/// Problems to inject into every response.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Faults {
+    pub(crate) latency: Duration,
+    pub(crate) not_found: bool,
+    pub(crate) malformed: bool,
+}
+
+enum Puzzles {
+    Archive(HashMap<NaiveDate, Puzzle>),
+    /// A made-up puzzle for every date up to today, with answers from the answer list.
+    Generated,
+}
+
+impl Puzzles {
+    fn get(&self, date: NaiveDate) -> Option<Puzzle> {
+        match self {
+            Puzzles::Archive(puzzles) => puzzles.get(&date).cloned(),
+            Puzzles::Generated if date < FIRST_WORDLE_DATE || date > manager::today() => None,
+            Puzzles::Generated => {
+                let number = date_to_wordle_number(date);
+                Some(Puzzle {
+                    id: number + 1,
+                    solution: wordle::GameInfo::seeded(number.into()).word.reveal(),
+                    print_date: date,
+                    days_since_launch: Some(number),
+                    editor: None,
+                })
+            }
+        }
+    }
+}
+
+pub(crate) struct Server {
+    puzzles: Puzzles,
+    faults: Faults,
+}
+
+impl Server {
+    pub(crate) fn generated(faults: Faults) -> Self {
+        Self {
+            puzzles: Puzzles::Generated,
+            faults,
+        }
+    }
+
+    /// Serves the puzzles in a JSON file holding an array of them, in the NYT's shape.
+    pub(crate) fn from_archive(path: &Path, faults: Faults) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let puzzles: Vec<Puzzle> = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to decode {}", path.display()))?;
+
+        Ok(Server::from_puzzles(puzzles, faults))
+    }
+
+    pub(crate) fn from_puzzles(puzzles: Vec<Puzzle>, faults: Faults) -> Self {
+        Self {
+            puzzles: Puzzles::Archive(
+                puzzles
+                    .into_iter()
+                    .map(|puzzle| (puzzle.print_date, puzzle))
+                    .collect(),
+            ),
+            faults,
+        }
+    }
+
+    /// Starts serving on a free local port, returning the source to fetch puzzles from.
+    #[cfg(test)]
+    pub(crate) async fn spawn(self) -> String {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let source = format!("http://{}{PATH}", listener.local_addr().unwrap());
+        tokio::spawn(self.run(listener));
+        source
+    }
+
+    pub(crate) async fn run(self, listener: TcpListener) -> anyhow::Result<()> {
+        let server = Arc::new(self);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let server = server.clone();
+            tokio::spawn(async move {
+                let _ = server.respond(stream).await;
+            });
+        }
+    }
+
+    async fn respond(&self, mut stream: TcpStream) -> anyhow::Result<()> {
+        let mut reader = BufReader::new(&mut stream);
+        let mut request = String::new();
+        reader.read_line(&mut request).await?;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+                break;
+            }
+        }
+
+        time::sleep(self.faults.latency).await;
+
+        let (status, body) = match self.route(&request) {
+            Some(_) if self.faults.not_found => ("404 Not Found", "Not Found".to_string()),
+            Some(_) if self.faults.malformed => ("200 OK", "{\"solution\": ".to_string()),
+            Some(puzzle) => ("200 OK", serde_json::to_string(&puzzle)?),
+            None => ("404 Not Found", "Not Found".to_string()),
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+            Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    fn route(&self, request: &str) -> Option<Puzzle> {
+        let mut parts = request.split_whitespace();
+        if parts.next()? != "GET" {
+            return None;
+        }
+        let date = parts
+            .next()?
+            .strip_prefix(PATH)?
+            .strip_prefix('/')?
+            .strip_suffix(".json")?
+            .parse()
+            .ok()?;
+        self.puzzles.get(date)
+    }
+}
+
+pub(crate) async fn run(port: u16, archive: Option<&Path>, faults: Faults) -> anyhow::Result<()> {
+    let server = match archive {
+        Some(path) => Server::from_archive(path, faults)?,
+        None => Server::generated(faults),
+    };
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to listen on port {port}"))?;
+
+    let source = format!("http://{}{PATH}", listener.local_addr()?);
+    println!("Serving puzzles at {source}");
+    println!("Play against them with `wordle --source {source}`");
+    server.run(listener).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as Days;
+
+    use super::*;
+    use crate::{
+        SaveData,
+        fetch::{Client, FetchError},
+        manager::GameManager,
+        puzzles::PuzzleDb,
+        wordle::GameInfo,
+    };
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_generated_puzzles() {
+        let source = Server::generated(Faults::default()).spawn().await;
+
+        let info = GameInfo::at(&source, date()).await.unwrap();
+        assert_eq!(info.number, 1000);
+        assert_eq!(info.word, GameInfo::seeded(1000).word);
+
+        let tomorrow = manager::today() + Days::days(1);
+        let result = GameInfo::at(&source, tomorrow).await;
+        assert!(matches!(result, Err(FetchError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn serves_archive_file() {
+        let path = std::env::temp_dir().join(format!("wordle-archive-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"[{"id":1,"solution":"crane","print_date":"2024-03-15","days_since_launch":1000,"editor":"Tracy Bennett"}]"#,
+        )
+        .unwrap();
+        let server = Server::from_archive(&path, Faults::default());
+        fs::remove_file(&path).unwrap();
+        let source = server.unwrap().spawn().await;
+
+        let info = GameInfo::at(&source, date()).await.unwrap();
+        assert_eq!(info.word.reveal(), "crane");
+        assert_eq!(info.editor.as_deref(), Some("Tracy Bennett"));
+
+        let result = GameInfo::at(&source, date() + Days::days(1)).await;
+        assert!(matches!(result, Err(FetchError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn injects_not_found() {
+        let faults = Faults {
+            not_found: true,
+            ..Faults::default()
+        };
+        let source = Server::generated(faults).spawn().await;
+
+        let result = GameInfo::at(&source, date()).await;
+        assert!(matches!(result, Err(FetchError::NotFound)));
+        assert!(
+            GameManager::open(&source, SaveData::in_memory(), PuzzleDb::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn injects_malformed_json() {
+        let faults = Faults {
+            malformed: true,
+            ..Faults::default()
+        };
+        let source = Server::generated(faults).spawn().await;
+
+        let result = GameInfo::at(&source, date()).await;
+        assert!(matches!(result, Err(FetchError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn injects_latency() {
+        let faults = Faults {
+            latency: Duration::from_millis(300),
+            ..Faults::default()
+        };
+        let source = Server::generated(faults).spawn().await;
+
+        let impatient = Client::new(Duration::from_millis(100), Duration::from_millis(1));
+        let result = GameInfo::fetch(&impatient, &source, date()).await;
+        assert!(matches!(result, Err(FetchError::Network(e)) if e.is_timeout()));
+
+        assert!(GameInfo::at(&source, date()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn game_manager_end_to_end() {
+        let source = Server::generated(Faults::default()).spawn().await;
+        let mut game = GameManager::open(&source, SaveData::in_memory(), PuzzleDb::default())
+            .await
+            .unwrap();
+        let today = manager::today();
+        assert_eq!(game.date, today);
+        assert_eq!(game.info.number, date_to_wordle_number(today));
+
+        game.next().await.unwrap();
+        assert_eq!(game.date, today);
+
+        game.previous().await.unwrap();
+        assert_eq!(game.date, today - Days::days(1));
+        let word = game.info.word.reveal();
+        let guess = wordle::answers().find(|&answer| answer != word).unwrap();
+        game.guess(guess).unwrap();
+        game.save();
+
+        game.first().await.unwrap();
+        assert_eq!(game.info.number, 0);
+        assert!(!game.has_started());
+
+        game.goto(today - Days::days(1)).await.unwrap();
+        assert_eq!(game.index, (1, 0));
+        assert_eq!(game.info.word.reveal(), word);
+    }
+}
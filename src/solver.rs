@@ -0,0 +1,79 @@
+use clap::ValueEnum;
+
+use crate::wordle;
+
+pub(crate) const SOLVED: u8 = 242;
+
+/// Colors `guess` gets against `answer`, packed in base 3 with the first letter lowest.
+pub(crate) fn pattern(guess: &str, answer: &str) -> u8 {
+    wordle::score(guess, answer)
+        .into_iter()
+        .rev()
+        .fold(0, |acc, color| acc * 3 + color as u8)
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum Solver {
+    /// Guess the first answer that's still possible
+    Naive,
+    /// Guess whatever leaves the fewest answers in the worst case
+    Minimax,
+    /// Guess whatever narrows the answers down the most on average
+    Entropy,
+}
+
+impl Solver {
+    /// Lower is better.
+    fn cost(self, guess: &str, candidates: &[&str]) -> f64 {
+        let mut counts = [0u32; 243];
+        for answer in candidates {
+            counts[pattern(guess, answer) as usize] += 1;
+        }
+
+        match self {
+            Solver::Naive => 0.0,
+            Solver::Minimax => counts.into_iter().max().unwrap_or_default() as f64,
+            Solver::Entropy => counts
+                .into_iter()
+                .filter(|&n| n > 0)
+                .map(|n| n as f64 * (n as f64).log2())
+                .sum(),
+        }
+    }
+
+    pub(crate) fn guess<'a>(self, candidates: &[&'a str], guesses: &[&'a str]) -> &'a str {
+        if candidates.len() <= 2 || matches!(self, Solver::Naive) {
+            return candidates[0];
+        }
+
+        let mut best = ((f64::INFINITY, true), candidates[0]);
+        for &guess in guesses {
+            let key = (self.cost(guess, candidates), !candidates.contains(&guess));
+            if key < best.0 {
+                best = (key, guess);
+            }
+        }
+        best.1
+    }
+
+    /// Number of guesses it takes to find `answer`, if it's found within six.
+    pub(crate) fn solve<'a>(
+        self,
+        answer: &str,
+        opener: &'a str,
+        answers: &[&'a str],
+    ) -> Option<usize> {
+        let mut candidates = answers.to_vec();
+        let mut guess = opener;
+
+        for turn in 1..=6 {
+            let colors = pattern(guess, answer);
+            if colors == SOLVED {
+                return Some(turn);
+            }
+            candidates.retain(|c| pattern(guess, c) == colors);
+            guess = self.guess(&candidates, answers);
+        }
+        None
+    }
+}
@@ -0,0 +1,309 @@
+use std::io;
+
+use anyhow::Context;
+use crossterm::event::{Event, EventStream};
+use futures::StreamExt;
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{TcpListener, TcpStream, ToSocketAddrs, tcp::OwnedReadHalf},
+    sync::watch,
+};
+
+use crate::{
+    Message, RunningState,
+    config::config,
+    widget::Board,
+    wordle::{Answer, Game, GameInfo, Row},
+};
+
+pub(crate) const DEFAULT_ADDRESS: &str = "127.0.0.1:7778";
+
+/// What spectators see of a game. The answer is left out until the game is over.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+struct Snapshot {
+    info: GameInfo,
+    grid: [Row; 6],
+    index: (usize, usize),
+    finished: bool,
+}
+
+impl Snapshot {
+    fn new(game: &Game, hide_letters: bool) -> Self {
+        let finished = game.has_finished();
+        let info = GameInfo {
+            word: Answer::new(&game.answer().unwrap_or_default()),
+            ..game.info.clone()
+        };
+
+        let mut snapshot = Self {
+            info,
+            grid: game.grid,
+            index: game.index,
+            finished,
+        };
+        if hide_letters {
+            snapshot.hide_letters();
+        }
+        snapshot
+    }
+
+    /// Blanks every letter, including the answer, leaving only the colors.
+    fn hide_letters(&mut self) {
+        for letter in self.grid.iter_mut().flat_map(|row| &mut row.letters) {
+            letter.char = ' ';
+        }
+        self.info.word = Answer::new("");
+    }
+
+    fn into_game(mut self, hide_letters: bool) -> Game {
+        if hide_letters {
+            self.hide_letters();
+        }
+        let mut game = Game::from(self.info);
+        game.grid = self.grid;
+        game.index = self.index;
+        game
+    }
+}
+
+/// Sends the game to everyone watching whenever it changes.
+pub(crate) struct Publisher {
+    snapshots: watch::Sender<Snapshot>,
+    /// Letters are blanked before anything is sent.
+    hide_letters: bool,
+}
+
+impl Publisher {
+    /// Starts accepting spectators on `addr`.
+    pub(crate) async fn bind(addr: &str, game: &Game, hide_letters: bool) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to publish on {addr}"))?;
+        Ok(Publisher::listen(listener, game, hide_letters))
+    }
+
+    fn listen(listener: TcpListener, game: &Game, hide_letters: bool) -> Self {
+        let (snapshots, receiver) = watch::channel(Snapshot::new(game, hide_letters));
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Publisher::stream(receiver.clone(), stream));
+            }
+        });
+
+        Self {
+            snapshots,
+            hide_letters,
+        }
+    }
+
+    async fn stream(
+        mut snapshots: watch::Receiver<Snapshot>,
+        mut stream: TcpStream,
+    ) -> io::Result<()> {
+        loop {
+            let mut line = serde_json::to_vec(&*snapshots.borrow_and_update())?;
+            line.push(b'\n');
+            stream.write_all(&line).await?;
+
+            if snapshots.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    pub(crate) fn publish(&self, game: &Game) {
+        let snapshot = Snapshot::new(game, self.hide_letters);
+        self.snapshots.send_if_modified(|current| {
+            let changed = *current != snapshot;
+            if changed {
+                *current = snapshot;
+            }
+            changed
+        });
+    }
+}
+
+/// A spectator's connection to a published game.
+struct Watcher {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+}
+
+impl Watcher {
+    async fn connect(addr: impl ToSocketAddrs) -> anyhow::Result<Self> {
+        let (reader, _) = TcpStream::connect(addr).await?.into_split();
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+        })
+    }
+
+    /// Waits for the game to change, or `None` once it's no longer published.
+    async fn next(&mut self) -> anyhow::Result<Option<Snapshot>> {
+        match self.lines.next_line().await? {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Shows the game published at `addr` full screen until it stops or the spectator quits.
+pub(crate) async fn watch(addr: &str, hide_letters: bool) -> anyhow::Result<()> {
+    let mut watcher = Watcher::connect(addr)
+        .await
+        .with_context(|| format!("Failed to connect to {addr}"))?;
+    let mut game = watcher
+        .next()
+        .await?
+        .with_context(|| format!("Nothing is published at {addr}"))?
+        .into_game(hide_letters);
+
+    let view = |frame: &mut Frame, game: &Game, connected: bool| {
+        let notice = if connected {
+            format!("Watching {addr}")
+        } else {
+            "The game is no longer published".to_string()
+        };
+        let board = Board {
+            game,
+            notice: (!game.has_finished() || !connected).then_some(notice.as_str()),
+            animations: &[],
+            countdown: None,
+        };
+        frame.render_widget(board, frame.area());
+    };
+
+    let mut terminal = ratatui::init();
+    let mut events = EventStream::new();
+    let mut connected = true;
+    let mut running_state = RunningState::Running;
+
+    while running_state == RunningState::Running {
+        terminal
+            .draw(|f| view(f, &game, connected))
+            .expect("failed to draw frame");
+
+        tokio::select! {
+            event = events.next() => match event {
+                Some(event) => {
+                    if let Event::Key(e) = event.expect("failed to read event")
+                        && e.is_press()
+                        && let Some(Message::Quit | Message::Escape) =
+                            config().keys.message(&e, false)
+                    {
+                        running_state = RunningState::Done;
+                    }
+                }
+                None => running_state = RunningState::Done,
+            },
+            snapshot = watcher.next(), if connected => match snapshot {
+                Ok(Some(snapshot)) => game = snapshot.into_game(hide_letters),
+                Ok(None) | Err(_) => connected = false,
+            },
+        }
+    }
+    ratatui::restore();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> Game {
+        Game::from(GameInfo {
+            id: 1,
+            number: 1000,
+            word: Answer::new("crane"),
+            date_string: "2024-03-15".to_string(),
+            editor: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn mirrors_the_game() {
+        let mut game = game();
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let publisher = Publisher::listen(listener, &game, false);
+
+        let mut watcher = Watcher::connect(addr).await.unwrap();
+        let snapshot = watcher.next().await.unwrap().unwrap();
+        assert_eq!(snapshot.index, (0, 0));
+
+        game.guess("trace").unwrap();
+        publisher.publish(&game);
+        let snapshot = watcher.next().await.unwrap().unwrap();
+        assert_eq!(snapshot.index, (1, 0));
+        assert_eq!(snapshot.grid[0], game.grid[0]);
+        assert!(!snapshot.finished);
+        assert_eq!(snapshot.info.word.reveal(), "");
+
+        // Unchanged games aren't sent again
+        publisher.publish(&game);
+        game.add_char('C');
+        publisher.publish(&game);
+        let snapshot = watcher.next().await.unwrap().unwrap();
+        assert_eq!(snapshot.index, (1, 1));
+
+        game.guess("crane").unwrap();
+        publisher.publish(&game);
+        let snapshot = watcher.next().await.unwrap().unwrap();
+        assert!(snapshot.finished);
+        assert_eq!(snapshot.into_game(false).answer().as_deref(), Some("crane"));
+
+        drop(publisher);
+        assert!(watcher.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn hides_letters_before_publishing() {
+        let mut game = game();
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let publisher = Publisher::listen(listener, &game, true);
+        let (reader, _) = TcpStream::connect(addr).await.unwrap().into_split();
+        let mut lines = BufReader::new(reader).lines();
+        lines.next_line().await.unwrap().unwrap();
+
+        game.guess("trace").unwrap();
+        game.add_char('S');
+        publisher.publish(&game);
+        let line = lines.next_line().await.unwrap().unwrap();
+        assert!(!line.contains("\"T\"") && !line.contains("\"S\""), "{line}");
+
+        game.guess("crane").unwrap();
+        publisher.publish(&game);
+        let line = lines.next_line().await.unwrap().unwrap();
+        let snapshot: Snapshot = serde_json::from_str(&line).unwrap();
+        assert!(snapshot.finished);
+        assert_eq!(snapshot.info.word.reveal(), "");
+        assert!(!line.contains("\"C\""), "{line}");
+        assert_eq!(
+            snapshot.grid[1].letters.map(|l| l.color),
+            game.grid[1].letters.map(|l| l.color)
+        );
+    }
+
+    #[test]
+    fn hides_letters() {
+        let mut game = game();
+        game.guess("trace").unwrap();
+        game.add_char('C');
+
+        let hidden = Snapshot::new(&game, false).into_game(true);
+        assert!(
+            hidden
+                .grid
+                .iter()
+                .flat_map(|row| row.letters)
+                .all(|l| l.char == ' ')
+        );
+        assert_eq!(
+            hidden.grid[0].letters.map(|l| l.color),
+            game.grid[0].letters.map(|l| l.color)
+        );
+        assert_eq!(hidden.index, (1, 1));
+    }
+}
@@ -1,22 +1,133 @@
-use crate::SaveData;
+use crate::{
+    SaveData,
+    manager::{date_to_wordle_number, today},
+    save::GameKind,
+};
 
 #[derive(Default)]
 pub(crate) struct Stats {
     pub(crate) attempted: usize,
     pub(crate) won: [usize; 6],
+    /// Puzzles won in a row, up to today's or yesterday's.
+    pub(crate) current_streak: usize,
+    pub(crate) max_streak: usize,
+}
+
+impl Stats {
+    pub(crate) fn win_percentage(&self) -> f32 {
+        if self.attempted == 0 {
+            return 0.0;
+        }
+        (self.won.iter().sum::<usize>() as f32 / self.attempted as f32 * 100.0).round()
+    }
 }
 
 impl SaveData {
-    pub(crate) fn stats(&self) -> Stats {
+    pub(crate) fn stats(&self, kind: GameKind) -> Stats {
+        self.stats_as_of(kind, date_to_wordle_number(today()))
+    }
+
+    /// Stats of one kind of game as they were on the day of puzzle `latest`. Only daily puzzles
+    /// have streaks.
+    pub(crate) fn stats_as_of(&self, kind: GameKind, latest: u32) -> Stats {
         let mut stats = Stats::default();
+        let mut results = Vec::new();
 
-        for game in self.games().filter(|game| game.has_finished()) {
+        for game in self.games_of(kind).filter(|game| game.has_finished()) {
             stats.attempted += 1;
             if let Some(guesses) = game.won_in() {
                 stats.won[guesses - 1] += 1;
             }
+            if kind == GameKind::Daily && game.info.number <= latest {
+                results.push((game.info.number, game.won_in().is_some()));
+            }
+        }
+
+        results.sort_unstable();
+        let mut streak = 0;
+        let mut previous = None;
+        for &(number, won) in &results {
+            streak = match previous {
+                _ if !won => 0,
+                Some(previous) if previous + 1 == number => streak + 1,
+                _ => 1,
+            };
+            stats.max_streak = stats.max_streak.max(streak);
+            previous = Some(number);
+        }
+        // Today's puzzle not being played yet doesn't break the streak
+        if previous.is_some_and(|number| number + 1 >= latest) {
+            stats.current_streak = streak;
         }
 
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        save::GameId,
+        wordle::{Answer, Game, GameInfo},
+    };
+
+    use super::*;
+
+    fn save_data(games: &[(u32, &str)]) -> SaveData {
+        let mut save_data = SaveData::in_memory();
+        for &(number, guess) in games {
+            let mut game = Game::from(GameInfo {
+                id: 0,
+                number,
+                word: Answer::new("crane"),
+                date_string: String::new(),
+                editor: None,
+            });
+            for _ in 0..6 {
+                game.guess(guess).unwrap();
+            }
+            save_data.save(GameId::Daily(number), &game);
+        }
+        save_data
+    }
+
+    #[test]
+    fn counts_streaks() {
+        let save_data = save_data(&[
+            (1, "crane"),
+            (2, "crane"),
+            (3, "crane"),
+            (4, "trace"),
+            (5, "crane"),
+            (7, "crane"),
+            (8, "crane"),
+        ]);
+
+        let stats = save_data.stats_as_of(GameKind::Daily, 9);
+        assert_eq!(stats.max_streak, 3);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.attempted, 7);
+
+        assert_eq!(save_data.stats_as_of(GameKind::Daily, 10).current_streak, 0);
+        assert_eq!(save_data.stats_as_of(GameKind::Daily, 5).current_streak, 1);
+    }
+
+    #[test]
+    fn counts_kinds_apart() {
+        let mut save_data = save_data(&[(1, "crane"), (2, "trace")]);
+        let mut game = Game::from(GameInfo::seeded(42));
+        game.info.word = Answer::new("crane");
+        game.guess("trace").unwrap();
+        game.guess("crane").unwrap();
+        save_data.save(GameId::Practice(42), &game);
+
+        let daily = save_data.stats_as_of(GameKind::Daily, 2);
+        assert_eq!(daily.attempted, 2);
+        assert_eq!(daily.won, [1, 0, 0, 0, 0, 0]);
+        let practice = save_data.stats_as_of(GameKind::Practice, 2);
+        assert_eq!(practice.attempted, 1);
+        assert_eq!(practice.won, [0, 1, 0, 0, 0, 0]);
+        assert_eq!(practice.max_streak, 0);
+        assert_eq!(save_data.stats_as_of(GameKind::Challenge, 2).attempted, 0);
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::LazyLock;
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Colors, config},
+    wordle,
+};
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    let config = config();
+    Theme::from(config.theme).with_colors(&config.colors)
+});
+
+pub(crate) fn theme() -> &'static Theme {
+    &THEME
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ThemeName {
+    #[default]
+    Default,
+    HighContrast,
+    Monochrome,
+    Light,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Theme {
+    pub(crate) empty: Style,
+    pub(crate) gray: Style,
+    pub(crate) yellow: Style,
+    pub(crate) green: Style,
+    pub(crate) symbols: Option<[char; 3]>,
+    pub(crate) bar: Style,
+    pub(crate) bar_highlight: Style,
+}
+
+impl Theme {
+    pub(crate) fn style(&self, color: Option<wordle::Color>) -> Style {
+        match color {
+            None => self.empty,
+            Some(wordle::Color::Gray) => self.gray,
+            Some(wordle::Color::Yellow) => self.yellow,
+            Some(wordle::Color::Green) => self.green,
+        }
+    }
+
+    pub(crate) fn symbol(&self, color: Option<wordle::Color>) -> Option<char> {
+        let symbols = self.symbols?;
+        Some(symbols[color? as usize])
+    }
+
+    fn tiles(text: Color, gray: Color, yellow: Color, green: Color) -> Self {
+        let tile = |bg| Style::new().fg(text).bg(bg);
+        Self {
+            empty: Style::new().fg(text),
+            gray: tile(gray),
+            yellow: tile(yellow),
+            green: tile(green),
+            symbols: None,
+            bar: Style::new().fg(gray),
+            bar_highlight: Style::new().fg(green),
+        }
+    }
+
+    fn with_colors(mut self, colors: &Colors) -> Self {
+        for (style, bg) in [
+            (&mut self.gray, colors.gray),
+            (&mut self.yellow, colors.yellow),
+            (&mut self.green, colors.green),
+        ] {
+            if let Some(bg) = bg {
+                *style = style.bg(bg);
+            }
+        }
+        if let Some(text) = colors.text {
+            for style in [
+                &mut self.empty,
+                &mut self.gray,
+                &mut self.yellow,
+                &mut self.green,
+            ] {
+                *style = style.fg(text);
+            }
+        }
+        self.bar = self.gray.bg.map_or(self.bar, |bg| self.bar.fg(bg));
+        self.bar_highlight = self
+            .green
+            .bg
+            .map_or(self.bar_highlight, |bg| self.bar_highlight.fg(bg));
+        self
+    }
+}
+
+impl From<ThemeName> for Theme {
+    fn from(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Default => {
+                Theme::tiles(Color::White, Color::DarkGray, Color::Yellow, Color::Green)
+            }
+            ThemeName::HighContrast => Theme::tiles(
+                Color::White,
+                Color::DarkGray,
+                Color::Rgb(133, 192, 249),
+                Color::Rgb(245, 121, 58),
+            ),
+            ThemeName::Light => Theme {
+                empty: Style::new().fg(Color::Black).bg(Color::Rgb(211, 214, 218)),
+                ..Theme::tiles(
+                    Color::White,
+                    Color::Rgb(120, 124, 126),
+                    Color::Rgb(201, 180, 88),
+                    Color::Rgb(106, 170, 100),
+                )
+            },
+            ThemeName::Monochrome => Theme {
+                empty: Style::new(),
+                gray: Style::new().add_modifier(Modifier::DIM | Modifier::CROSSED_OUT),
+                yellow: Style::new().add_modifier(Modifier::UNDERLINED),
+                green: Style::new().add_modifier(Modifier::REVERSED),
+                symbols: Some(['\u{b7}', '\u{25cb}', '\u{25cf}']),
+                bar: Style::new().add_modifier(Modifier::DIM),
+                bar_highlight: Style::new(),
+            },
+        }
+    }
+}
@@ -1,119 +1,234 @@
-use std::{array, borrow::Cow, collections::HashSet};
+use std::{array, borrow::Cow, collections::HashSet, rc::Rc};
 
+use chrono::TimeDelta;
 use ratatui::{
-    layout::{Constraint, Direction, Flex, Layout},
-    prelude::{self, Color, Rect},
+    layout::{Constraint, Direction, Flex, Layout, Position},
+    prelude::{self, Rect},
     style::{Style, Stylize},
     text::Line,
-    widgets::{Bar, BarChart, BarGroup, Block, Padding, Paragraph, Widget},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Cell, Padding, Paragraph, Row, StatefulWidget, Table, Tabs,
+        Widget, Wrap,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::Animation,
+    archive::{Archive, Filter, Status},
+    config::{KeyMap, config},
+    race::Player,
+    stats::Stats,
+    theme::{Theme, theme},
+    wordle,
 };
 
-use crate::{stats::Stats, wordle};
-
-impl From<&wordle::Color> for Color {
-    fn from(value: &wordle::Color) -> Self {
-        match value {
-            wordle::Color::Gray => Color::DarkGray,
-            wordle::Color::Green => Color::Green,
-            wordle::Color::Yellow => Color::Yellow,
+pub(crate) fn render_scrolled(
+    widget: impl Widget,
+    height: u16,
+    scroll: &mut u16,
+    area: Rect,
+    buf: &mut prelude::Buffer,
+) {
+    let height = height.max(area.height);
+    *scroll = (*scroll).min(height - area.height);
+
+    let mut full = prelude::Buffer::empty(Rect { height, ..area });
+    widget.render(full.area, &mut full);
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            buf[(x, y)] = full[(x, y + *scroll)].clone();
         }
     }
 }
 
+fn render_tile(text: &str, color: Option<wordle::Color>, area: Rect, buf: &mut prelude::Buffer) {
+    let block = Block::new()
+        .style(theme().style(color))
+        .padding(Padding::top(area.height / 2));
+
+    let text = Paragraph::new(text).bold().centered().block(block);
+    text.render(area, buf);
+
+    if let Some(symbol) = theme().symbol(color)
+        && area.height >= 3
+        && let Some(cell) = buf.cell_mut((area.right() - 1, area.bottom() - 1))
+    {
+        cell.set_char(symbol);
+    }
+}
+
 impl Widget for &wordle::Letter {
     fn render(self, area: Rect, buf: &mut prelude::Buffer)
     where
         Self: Sized,
     {
-        let mut block = Block::new()
-            .fg(Color::White)
-            .padding(Padding::top(area.height / 2));
+        render_tile(&self.char.to_string(), self.color, area, buf);
+    }
+}
 
-        if let Some(color) = &self.color {
-            block = block.bg(color);
+impl Widget for &wordle::Row {
+    fn render(self, area: Rect, buf: &mut prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        for (area, letter) in letter_areas(area).iter().zip(&self.letters) {
+            letter.render(*area, buf);
         }
+    }
+}
 
-        let text = Paragraph::new(self.char.to_string())
-            .bold()
-            .centered()
-            .block(block);
-        text.render(area, buf);
+fn letter_areas(area: Rect) -> [Rect; 5] {
+    Layout::horizontal([Constraint::Percentage(20); 5])
+        .flex(Flex::Start)
+        .spacing(1)
+        .areas(area)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Key {
+    Letter(wordle::Letter),
+    Enter,
+    Backspace,
+}
+
+impl Key {
+    fn width(&self) -> Constraint {
+        match self {
+            Key::Letter(_) => Constraint::Fill(2),
+            Key::Enter | Key::Backspace => Constraint::Fill(3),
+        }
     }
 }
 
-impl Widget for &wordle::Row {
+impl From<char> for Key {
+    fn from(char: char) -> Self {
+        Key::Letter(wordle::Letter { char, color: None })
+    }
+}
+
+impl Widget for &Key {
     fn render(self, area: Rect, buf: &mut prelude::Buffer)
     where
         Self: Sized,
     {
-        let layout = Layout::horizontal([Constraint::Percentage(20); 5])
-            .flex(Flex::Start)
-            .spacing(1);
-        for (area, letter) in layout.areas::<5>(area).iter().zip(&self.letters) {
-            letter.render(*area, buf);
+        match self {
+            Key::Letter(letter) => letter.render(area, buf),
+            Key::Enter => render_tile("ENTER", None, area, buf),
+            Key::Backspace => render_tile("\u{232b}", None, area, buf),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+}
+
+impl KeyboardLayout {
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Qwerty => ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"],
+            KeyboardLayout::Azerty => ["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"],
+            KeyboardLayout::Qwertz => ["QWERTZUIOP", "ASDFGHJKL", "YXCVBNM"],
+            KeyboardLayout::Dvorak => ["PYFGCRL", "AOEUIDHTNS", "QJKXBMWVZ"],
         }
     }
 }
 
-struct KeyboardRow<const N: usize> {
-    letters: [wordle::Letter; N],
+struct KeyboardRow {
+    keys: Vec<Key>,
 }
 
-impl<const N: usize> KeyboardRow<N> {
-    fn from_chars(chars: [char; N]) -> Self {
+impl KeyboardRow {
+    fn from_chars(chars: &str) -> Self {
         Self {
-            letters: chars.map(|c| wordle::Letter {
-                char: c,
-                color: None,
-            }),
+            keys: chars.chars().map(Key::from).collect(),
         }
     }
 
     fn set_color(&mut self, char: char, color: Option<wordle::Color>) {
-        if let Some(letter) = self.letters.iter_mut().find(|l| l.char == char) {
-            if color > letter.color {
-                letter.color = color;
-            }
+        if let Some(Key::Letter(letter)) = self
+            .keys
+            .iter_mut()
+            .find(|k| matches!(k, Key::Letter(l) if l.char == char))
+            && color > letter.color
+        {
+            letter.color = color;
         }
     }
+
+    fn areas(&self, area: Rect) -> Rc<[Rect]> {
+        Layout::horizontal(self.keys.iter().map(Key::width))
+            .flex(Flex::Start)
+            .split(area)
+    }
+
+    fn key_at(&self, area: Rect, position: Position) -> Option<Key> {
+        self.areas(area)
+            .iter()
+            .zip(&self.keys)
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, key)| *key)
+    }
 }
 
-impl<const N: usize> Widget for &KeyboardRow<N> {
+impl Widget for &KeyboardRow {
     fn render(self, area: Rect, buf: &mut prelude::Buffer)
     where
         Self: Sized,
     {
-        let layout = Layout::horizontal([Constraint::Min(1); N]).flex(Flex::Start);
-        for (area, letter) in layout.areas::<N>(area).iter().zip(&self.letters) {
-            letter.render(*area, buf);
+        for (area, key) in self.areas(area).iter().zip(&self.keys) {
+            key.render(*area, buf);
         }
     }
 }
 
 pub(crate) struct Keyboard {
-    rows: (KeyboardRow<10>, KeyboardRow<9>, KeyboardRow<7>),
+    rows: [KeyboardRow; 3],
 }
 
 impl Keyboard {
     pub(crate) fn from_rows(rows: &[wordle::Row]) -> Self {
+        Keyboard::new(config().keyboard, rows)
+    }
+
+    pub(crate) fn new(layout: KeyboardLayout, rows: &[wordle::Row]) -> Self {
         let mut keyboard = Self {
-            rows: (
-                KeyboardRow::from_chars(['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P']),
-                KeyboardRow::from_chars(['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L']),
-                KeyboardRow::from_chars(['Z', 'X', 'C', 'V', 'B', 'N', 'M']),
-            ),
+            rows: layout.rows().map(KeyboardRow::from_chars),
         };
+        keyboard.rows[2].keys.insert(0, Key::Enter);
+        keyboard.rows[2].keys.push(Key::Backspace);
 
         for row in rows {
             for letter in row.letters {
-                keyboard.rows.0.set_color(letter.char, letter.color);
-                keyboard.rows.1.set_color(letter.char, letter.color);
-                keyboard.rows.2.set_color(letter.char, letter.color);
+                for keyboard_row in &mut keyboard.rows {
+                    keyboard_row.set_color(letter.char, letter.color);
+                }
             }
         }
 
         keyboard
     }
+
+    fn areas(area: Rect) -> [Rect; 3] {
+        Layout::vertical([Constraint::Min(1); 3])
+            .flex(Flex::Start)
+            .areas(area)
+    }
+
+    pub(crate) fn key_at(&self, area: Rect, position: Position) -> Option<Key> {
+        Keyboard::areas(area)
+            .into_iter()
+            .zip(&self.rows)
+            .find_map(|(area, row)| row.key_at(area, position))
+    }
 }
 
 impl Widget for &Keyboard {
@@ -121,28 +236,136 @@ impl Widget for &Keyboard {
     where
         Self: Sized,
     {
-        let layout = Layout::vertical([Constraint::Min(1); 3]).flex(Flex::Start);
-        let [area1, area2, area3] = layout.areas(area);
-        self.rows.0.render(area1, buf);
-        self.rows.1.render(area2, buf);
-        self.rows.2.render(area3, buf);
+        for (area, row) in Keyboard::areas(area).into_iter().zip(&self.rows) {
+            row.render(area, buf);
+        }
     }
 }
 
+pub(crate) struct Board<'a> {
+    pub(crate) game: &'a wordle::Game,
+    pub(crate) notice: Option<&'a str>,
+    pub(crate) animations: &'a [Animation],
+    pub(crate) countdown: Option<TimeDelta>,
+}
+
 impl Widget for &wordle::Game {
     fn render(self, area: Rect, buf: &mut prelude::Buffer)
     where
         Self: Sized,
     {
+        Board {
+            game: self,
+            notice: None,
+            animations: &[],
+            countdown: None,
+        }
+        .render(area, buf);
+    }
+}
+
+/// Smallest terminal the board fits in, with 1-row tiles and no keyboard.
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Density {
+    /// 3-row tiles and the full keyboard.
+    Full,
+    /// 1-row tiles and the keyboard.
+    Compact,
+    /// 1-row tiles and no keyboard.
+    Minimal,
+}
+
+impl Density {
+    fn fitting(area: Rect) -> Option<Self> {
+        if area.width >= 29 && area.height >= 30 {
+            Some(Density::Full)
+        } else if area.width >= MIN_WIDTH && area.height >= 13 {
+            Some(Density::Compact)
+        } else if area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT {
+            Some(Density::Minimal)
+        } else {
+            None
+        }
+    }
+
+    fn tile_height(self) -> u16 {
+        match self {
+            Density::Full => 3,
+            Density::Compact | Density::Minimal => 1,
+        }
+    }
+
+    fn grid_width(self) -> u16 {
+        match self {
+            Density::Full => 29,
+            Density::Compact | Density::Minimal => 19,
+        }
+    }
+
+    fn keyboard_height(self) -> u16 {
+        match self {
+            Density::Full | Density::Compact => 3,
+            Density::Minimal => 0,
+        }
+    }
+}
+
+pub(crate) enum Target {
+    Key(Key),
+    Previous,
+    Next,
+}
+
+struct BoardAreas {
+    density: Density,
+    previous: Rect,
+    title: Rect,
+    next: Rect,
+    game: Rect,
+    message: Rect,
+    keyboard: Rect,
+}
+
+impl Board<'_> {
+    pub(crate) fn fits(area: Rect) -> bool {
+        Density::fitting(area).is_some()
+    }
+
+    /// The longest title that fits beside the arrows.
+    fn title(&self, width: u16) -> String {
+        let info = &self.game.info;
+        [
+            info.title(),
+            format!("Wordle #{} - {}", info.number, info.date_string),
+        ]
+        .into_iter()
+        .find(|title| title.chars().count() as u16 + 6 <= width)
+        .unwrap_or_else(|| format!("#{}", info.number))
+    }
+
+    fn areas(&self, area: Rect) -> Option<BoardAreas> {
+        let density = Density::fitting(area)?;
+
         let layout = Layout::vertical([
             Constraint::Length(2),
             Constraint::Fill(1),
             Constraint::Length(2),
-            Constraint::Length(3),
+            Constraint::Length(density.keyboard_height()),
         ])
         .flex(Flex::Start);
 
-        let game_layout = Layout::horizontal([Constraint::Length(29)])
+        let title_layout = Layout::horizontal([
+            Constraint::Length(1),
+            Constraint::Length(self.title(area.width).chars().count() as u16),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .spacing(2);
+
+        let game_layout = Layout::horizontal([Constraint::Length(density.grid_width())])
             .flex(Flex::Center)
             .spacing(2);
 
@@ -151,50 +374,159 @@ impl Widget for &wordle::Game {
             .spacing(2);
 
         let [title_area, game_area, message_area, keyboard_area] = layout.areas(area);
-        let [game_area] = game_layout.areas(game_area);
-        let [keyboard_area] = keyboard_layout.areas(keyboard_area);
+        let [previous, title, next] = title_layout.areas(title_area);
+        let [game] = game_layout.areas(game_area);
+        let [keyboard] = keyboard_layout.areas(keyboard_area);
+
+        Some(BoardAreas {
+            density,
+            previous,
+            title,
+            next,
+            game,
+            message: message_area,
+            keyboard,
+        })
+    }
+
+    pub(crate) fn hit(&self, area: Rect, position: Position) -> Option<Target> {
+        let areas = self.areas(area)?;
 
-        let grid_layout = Layout::vertical([Constraint::Length(3); 6])
+        if areas.previous.contains(position) {
+            Some(Target::Previous)
+        } else if areas.next.contains(position) {
+            Some(Target::Next)
+        } else {
+            Keyboard::from_rows(&self.game.grid)
+                .key_at(areas.keyboard, position)
+                .map(Target::Key)
+        }
+    }
+}
+
+impl Widget for Board<'_> {
+    fn render(self, area: Rect, buf: &mut prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let game = self.game;
+        let Some(areas) = self.areas(area) else {
+            let text = format!("Terminal too small (need {MIN_WIDTH}x{MIN_HEIGHT})");
+            let lines = (text.len() as u16).div_ceil(area.width.max(1)) + 1;
+            let [area] = Layout::vertical([Constraint::Length(lines)])
+                .flex(Flex::Center)
+                .areas(area);
+            Paragraph::new(text)
+                .centered()
+                .wrap(Wrap { trim: true })
+                .render(area, buf);
+            return;
+        };
+
+        let grid_layout = Layout::vertical([Constraint::Length(areas.density.tile_height()); 6])
             .flex(Flex::Start)
-            .spacing(1);
+            .spacing(u16::from(areas.density == Density::Full));
+
+        let mut grid = game.grid;
+        for animation in self.animations {
+            for (col, letter) in grid[animation.row].letters.iter_mut().enumerate() {
+                if animation.hides(col) {
+                    letter.color = None;
+                }
+            }
+        }
 
-        for (area, row) in grid_layout
-            .areas::<6>(game_area)
+        for (i, (area, row)) in grid_layout
+            .areas::<6>(areas.game)
             .into_iter()
-            .zip(&self.grid)
+            .zip(&grid)
+            .enumerate()
         {
-            row.render(area, buf);
+            for (col, (mut area, letter)) in
+                letter_areas(area).into_iter().zip(&row.letters).enumerate()
+            {
+                for animation in self.animations.iter().filter(|a| a.row == i) {
+                    area = animation.transform(col, area);
+                }
+                letter.render(area.intersection(buf.area), buf);
+            }
         }
 
-        Keyboard::from_rows(&self.grid).render(keyboard_area, buf);
-
-        Paragraph::new(format!(
-            "Wordle #{} - {}",
-            self.info.number, self.info.date_string
-        ))
-        .bold()
-        .centered()
-        .render(title_area, buf);
-
-        let message: Cow<str> = if self.has_finished() {
-            match self.won_in() {
-                Some(1) => "Genius".into(),
-                Some(2) => "Magnificent".into(),
-                Some(3) => "Impressive".into(),
-                Some(4) => "Splendid".into(),
-                Some(5) => "Great".into(),
-                Some(6) => "Phew".into(),
-                None => self.info.word.to_uppercase().into(),
-                _ => unreachable!(),
-            }
+        Keyboard::from_rows(&grid).render(areas.keyboard, buf);
+
+        Line::from(self.title(area.width))
+            .bold()
+            .centered()
+            .render(areas.title, buf);
+        Line::from("\u{25c0}").render(areas.previous, buf);
+        Line::from("\u{25b6}").render(areas.next, buf);
+
+        let message: Cow<str> = if let Some(notice) = self.notice {
+            notice.into()
+        } else if let Some(result) = game.result().filter(|_| self.animations.is_empty()) {
+            result
         } else {
             "".into()
         };
 
-        Paragraph::new(message)
-            .bold()
-            .centered()
-            .render(message_area, buf);
+        let mut lines = vec![Line::from(message).bold()];
+        if let Some(countdown) = self.countdown.filter(|_| self.animations.is_empty()) {
+            let seconds = countdown.num_seconds().max(0);
+            lines.push(Line::from(format!(
+                "Next Wordle in {:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )));
+        }
+
+        Paragraph::new(lines).centered().render(areas.message, buf);
+    }
+}
+
+/// Size of an opponent's board in a race, border included.
+pub(crate) const MINI_GRID_WIDTH: u16 = 16;
+pub(crate) const MINI_GRID_HEIGHT: u16 = 8;
+
+/// An opponent's board, with colors but no letters.
+impl Widget for &Player {
+    fn render(self, area: Rect, buf: &mut prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let status = match self.solved_in() {
+            Some(guesses) => format!("Solved in {guesses}"),
+            None if self.has_finished() => "X/6".to_string(),
+            None if !self.connected => "Left".to_string(),
+            None => format!("{}/6", self.rows.len()),
+        };
+        let block = Block::bordered()
+            .title_top(Line::from(self.name.as_str()).bold().centered())
+            .title_bottom(Line::from(status).centered());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let rows = Layout::vertical([Constraint::Length(1); 6]).split(inner);
+        for (i, row_area) in rows.iter().enumerate() {
+            let tiles = Layout::horizontal([Constraint::Length(2); 5])
+                .flex(Flex::Center)
+                .spacing(1)
+                .split(*row_area);
+            for (col, tile) in tiles.iter().enumerate() {
+                let color = self.rows.get(i).map(|row| row[col]);
+                let text = match (color, theme().symbol(color)) {
+                    (None, _) => "\u{b7}".to_string(),
+                    (Some(_), symbol) => symbol.map(String::from).unwrap_or_default(),
+                };
+                render_tile(&text, color, *tile, buf);
+            }
+        }
+    }
+}
+
+impl Stats {
+    pub(crate) fn height(&self) -> u16 {
+        3 + 2 + 1 + 1 + self.won.len() as u16 + 1
     }
 }
 
@@ -216,10 +548,7 @@ impl Widget for &Stats {
             .centered()
             .block(Block::new().title_bottom(Line::from("Played").not_bold().centered()));
 
-        let win_percentage =
-            (self.won.iter().sum::<usize>() as f32 / self.attempted as f32 * 100.0).round();
-
-        let percent_block = Paragraph::new(format!("{win_percentage}"))
+        let percent_block = Paragraph::new(format!("{}", self.win_percentage()))
             .bold()
             .centered()
             .block(Block::new().title_bottom(Line::from("Win %").not_bold().centered()));
@@ -235,6 +564,7 @@ impl Widget for &Stats {
             .filter_map(|(i, c)| (*c == max).then_some(i))
             .collect::<HashSet<_>>();
 
+        let theme = theme();
         let mut bars: [Bar; 6] = array::from_fn(|_| Bar::default());
 
         for (i, bar) in bars.iter_mut().enumerate() {
@@ -242,14 +572,14 @@ impl Widget for &Stats {
                 .label(format!("{}", i + 1).into())
                 .value(self.won[i] as u64)
                 .style(if max_indices.contains(&i) {
-                    Style::new().green()
+                    theme.bar_highlight
                 } else {
-                    Style::new().dark_gray()
+                    theme.bar
                 })
                 .value_style(if max_indices.contains(&i) {
-                    Style::new().bold().white().on_green()
+                    theme.green.bold()
                 } else {
-                    Style::new().bold().white().on_dark_gray()
+                    theme.gray.bold()
                 })
         }
 
@@ -257,7 +587,10 @@ impl Widget for &Stats {
             .direction(Direction::Horizontal)
             .bar_width(1)
             .bar_gap(0)
-            .label_style(Style::new().white())
+            .label_style(Style {
+                bg: None,
+                ..theme.empty
+            })
             .data(BarGroup::default().bars(&bars))
             .max(max as u64);
 
@@ -270,3 +603,387 @@ impl Widget for &Stats {
         chart.render(chart_block_area, buf);
     }
 }
+
+impl Widget for &mut Archive {
+    fn render(self, area: Rect, buf: &mut prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        self.render_themed(area, buf, theme());
+    }
+}
+
+impl Archive {
+    fn render_themed(&mut self, area: Rect, buf: &mut prelude::Buffer, theme: &Theme) {
+        let [tabs_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
+                .spacing(1)
+                .areas(area);
+
+        Tabs::new(Filter::ALL.map(Filter::label))
+            .select(Filter::ALL.iter().position(|f| *f == self.filter))
+            .highlight_style(Style::new().bold().reversed())
+            .render(tabs_area, buf);
+
+        let rows = self
+            .rows()
+            .map(|entry| {
+                let (status, style) = match entry.status {
+                    Status::Unplayed => ("Unplayed", theme.empty),
+                    Status::InProgress => ("In progress", theme.yellow),
+                    Status::Won(_) => ("Won", theme.green),
+                    Status::Lost => ("Lost", theme.gray),
+                };
+                let guesses = match entry.status {
+                    Status::Unplayed => "-".to_string(),
+                    Status::Won(guesses) => format!("{guesses}/6"),
+                    Status::Lost => "X/6".to_string(),
+                    Status::InProgress => format!("{}/6", entry.guesses),
+                };
+                let answer = entry.answer.clone().unwrap_or("\u{2022}".repeat(5));
+
+                Row::new([
+                    Cell::from(format!("#{}", entry.number)),
+                    Cell::from(entry.date.format("%Y-%m-%d").to_string()),
+                    Cell::from(status).style(style),
+                    Cell::from(guesses),
+                    Cell::from(answer),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(7),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(["No.", "Date", "Status", "Guesses", "Answer"]).bold())
+        .row_highlight_style(Style::new().reversed())
+        .flex(Flex::Center);
+
+        StatefulWidget::render(table, table_area, buf, &mut self.state);
+    }
+}
+
+pub(crate) struct Help<'a> {
+    pub(crate) keys: &'a KeyMap,
+}
+
+impl Help<'_> {
+    pub(crate) fn height(&self) -> u16 {
+        let keys = self
+            .keys
+            .actions()
+            .iter()
+            .filter(|(_, keys, _)| !keys.is_empty())
+            .count();
+        4 + 1 + 9 + 1 + 2 + keys as u16
+    }
+
+    fn example(word: &str, index: usize, color: wordle::Color) -> wordle::Row {
+        let mut row = wordle::Row::default();
+        for (letter, char) in row.letters.iter_mut().zip(word.chars()) {
+            letter.char = char;
+        }
+        row.letters[index].color = Some(color);
+        row
+    }
+}
+
+impl Widget for Help<'_> {
+    fn render(self, area: Rect, buf: &mut prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let [rules_area, examples_area, keys_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(9),
+            Constraint::Fill(1),
+        ])
+        .spacing(1)
+        .areas(area);
+
+        Paragraph::new(vec![
+            Line::from("Guess the Wordle in 6 tries.").bold(),
+            Line::from("- Each guess must be a valid 5-letter word."),
+            Line::from("- The color of the tiles will change to show how close"),
+            Line::from("  your guess was to the word."),
+        ])
+        .render(rules_area, buf);
+
+        let examples = [
+            (
+                Help::example("WEARY", 0, wordle::Color::Green),
+                "W is in the word and in the correct spot.",
+            ),
+            (
+                Help::example("PILLS", 1, wordle::Color::Yellow),
+                "I is in the word but in the wrong spot.",
+            ),
+            (
+                Help::example("VAGUE", 3, wordle::Color::Gray),
+                "U is not in the word in any spot.",
+            ),
+        ];
+
+        let [title_area, examples_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(examples_area);
+        Line::from("Examples").bold().render(title_area, buf);
+
+        for (area, (row, text)) in Layout::vertical([Constraint::Length(3); 3])
+            .areas::<3>(examples_area)
+            .into_iter()
+            .zip(&examples)
+        {
+            let [row_area, text_area] = Layout::vertical([Constraint::Length(1); 2]).areas(area);
+            let [row_area] = Layout::horizontal([Constraint::Length(19)]).areas(row_area);
+            row.render(row_area, buf);
+            Line::from(*text).render(text_area, buf);
+        }
+
+        let mut rows = self
+            .keys
+            .actions()
+            .into_iter()
+            .filter(|(_, keys, _)| !keys.is_empty())
+            .map(|(description, keys, _)| {
+                let keys = keys
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Row::new([Cell::from(keys), Cell::from(description)])
+            })
+            .collect::<Vec<_>>();
+        rows.insert(0, Row::new(["A-Z", "Type a letter"]));
+
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+            .header(Row::new(["Keys", ""]).bold());
+        Widget::render(table, keys_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::{SaveData, config::Config, manager, save::GameId, theme::ThemeName};
+
+    fn render(widget: impl Widget, width: u16, height: u16) -> TestBackend {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(widget, frame.area()))
+            .unwrap();
+        terminal.backend().clone()
+    }
+
+    fn game(guesses: &[&str]) -> wordle::Game {
+        let mut game = wordle::Game::from(wordle::GameInfo {
+            id: 1,
+            number: 1000,
+            word: wordle::Answer::new("crane"),
+            date_string: "2024-03-15".to_string(),
+            editor: Some("Tracy Bennett".to_string()),
+        });
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    fn stats(won: [usize; 6], lost: usize) -> Stats {
+        Stats {
+            attempted: won.iter().sum::<usize>() + lost,
+            won,
+            ..Stats::default()
+        }
+    }
+
+    #[test]
+    fn letter() {
+        let empty = wordle::Letter::default();
+        let green = wordle::Letter {
+            char: 'A',
+            color: Some(wordle::Color::Green),
+        };
+        assert_snapshot!("letter_empty", render(&empty, 5, 3));
+        assert_snapshot!("letter_green", render(&green, 5, 3));
+        assert_snapshot!("letter_green_1x1", render(&green, 1, 1));
+    }
+
+    #[test]
+    fn row() {
+        let game = game(&["speed"]);
+        for (width, height) in [(29, 3), (14, 1), (3, 1)] {
+            assert_snapshot!(
+                format!("row_{width}x{height}"),
+                render(&game.grid[0], width, height)
+            );
+        }
+    }
+
+    #[test]
+    fn keyboard() {
+        let game = game(&["trace", "slate"]);
+        for (width, height) in [(40, 3), (20, 3), (10, 1)] {
+            assert_snapshot!(
+                format!("keyboard_{width}x{height}"),
+                render(
+                    &Keyboard::new(KeyboardLayout::Qwerty, &game.grid),
+                    width,
+                    height
+                )
+            );
+        }
+        for layout in [
+            KeyboardLayout::Azerty,
+            KeyboardLayout::Qwertz,
+            KeyboardLayout::Dvorak,
+        ] {
+            assert_snapshot!(
+                format!("keyboard_{layout:?}").to_lowercase(),
+                render(&Keyboard::new(layout, &[]), 40, 3)
+            );
+        }
+    }
+
+    #[test]
+    fn game_sizes() {
+        let game = game(&["trace", "slate"]);
+        for (width, height) in [
+            (80, 34),
+            (29, 30),
+            (40, 20),
+            (20, 13),
+            (20, 10),
+            (19, 10),
+            (20, 9),
+            (1, 1),
+        ] {
+            assert_snapshot!(
+                format!("game_{width}x{height}"),
+                render(&game, width, height)
+            );
+        }
+    }
+
+    #[test]
+    fn game_states() {
+        assert_snapshot!("game_new", render(&game(&[]), 50, 34));
+
+        let mut typing = game(&["trace"]);
+        for char in "SLA".chars() {
+            typing.add_char(char);
+        }
+        assert_snapshot!("game_typing", render(&typing, 50, 34));
+
+        assert_snapshot!(
+            "game_lost",
+            render(
+                &game(&["slate", "slate", "slate", "slate", "slate", "slate"]),
+                50,
+                34
+            )
+        );
+
+        let won = game(&["trace", "crane"]);
+        let board = Board {
+            game: &won,
+            notice: None,
+            animations: &[],
+            countdown: Some(TimeDelta::seconds(3 * 3600 + 25 * 60 + 7)),
+        };
+        assert_snapshot!("game_won_countdown", render(board, 50, 34));
+
+        let board = Board {
+            game: &typing,
+            notice: Some("Not in word list"),
+            animations: &[],
+            countdown: None,
+        };
+        assert_snapshot!("game_notice", render(board, 50, 34));
+    }
+
+    #[test]
+    fn opponent() {
+        use wordle::Color::{Gray as X, Green as G, Yellow as Y};
+
+        let mut player = Player::new(1, "alice");
+        assert_snapshot!(
+            "opponent_new",
+            render(&player, MINI_GRID_WIDTH, MINI_GRID_HEIGHT)
+        );
+        player.rows = vec![[X, Y, X, X, G], [G, G, G, G, G]];
+        assert_snapshot!(
+            "opponent_solved",
+            render(&player, MINI_GRID_WIDTH, MINI_GRID_HEIGHT)
+        );
+        player.rows.pop();
+        player.connected = false;
+        assert_snapshot!(
+            "opponent_left",
+            render(&player, MINI_GRID_WIDTH, MINI_GRID_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn stats_sizes() {
+        let stats = stats([0, 2, 5, 3, 1, 0], 1);
+        for (width, height) in [(46, 14), (20, 8), (4, 2)] {
+            assert_snapshot!(
+                format!("stats_{width}x{height}"),
+                render(&stats, width, height)
+            );
+        }
+    }
+
+    #[test]
+    fn stats_no_games() {
+        assert_snapshot!("stats_no_games", render(&stats([0; 6], 0), 46, 14));
+    }
+
+    #[test]
+    fn stats_all_lost() {
+        assert_snapshot!("stats_all_lost", render(&stats([0; 6], 3), 46, 14));
+    }
+
+    #[test]
+    fn archive() {
+        let mut save_data = SaveData::in_memory();
+        let mut won = game(&["trace", "crane"]);
+        won.info.number =
+            manager::date_to_wordle_number(NaiveDate::from_ymd_opt(2021, 6, 21).unwrap());
+        save_data.save(GameId::Daily(won.info.number), &won);
+        let mut started = game(&["slate"]);
+        started.info.number =
+            manager::date_to_wordle_number(NaiveDate::from_ymd_opt(2021, 6, 20).unwrap());
+        save_data.save(GameId::Daily(started.info.number), &started);
+
+        let mut archive = Archive::new(&save_data, NaiveDate::from_ymd_opt(2021, 6, 22).unwrap());
+        archive.select_date(NaiveDate::from_ymd_opt(2021, 6, 21).unwrap());
+        assert_snapshot!("archive", render(&mut archive, 48, 10));
+        assert_snapshot!("archive_tiny", render(&mut archive, 12, 3));
+
+        archive.select_date(manager::FIRST_WORDLE_DATE);
+        let mut buf = prelude::Buffer::empty(Rect::new(0, 0, 48, 6));
+        archive.render_themed(buf.area, &mut buf, &Theme::from(ThemeName::Monochrome));
+        insta::assert_debug_snapshot!("archive_monochrome", buf);
+    }
+
+    #[test]
+    fn help() {
+        let config = Config::default();
+        let help = Help { keys: &config.keys };
+        let height = help.height();
+        assert_snapshot!("help", render(help, 58, height));
+        assert_snapshot!("help_tiny", render(Help { keys: &config.keys }, 10, 4));
+    }
+}
@@ -1,7 +1,28 @@
-use chrono::{NaiveDate, Utc};
+use std::{borrow::Cow, fmt};
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    config::config,
+    fetch::{self, FetchError},
+    manager::wordle_number,
+};
+
+pub(crate) fn allowed() -> impl Iterator<Item = &'static str> {
+    include_str!("./wordlist.txt").lines()
+}
+
+pub(crate) fn is_allowed(word: &str) -> bool {
+    allowed().any(|w| w == word)
+}
+
+pub(crate) fn answers() -> impl Iterator<Item = &'static str> {
+    include_str!("./answers.txt").lines()
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum Color {
     #[default]
     Gray,
@@ -9,7 +30,7 @@ pub(crate) enum Color {
     Green,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 pub(crate) struct Letter {
     pub(crate) char: char,
     pub(crate) color: Option<Color>,
@@ -24,63 +45,204 @@ impl Default for Letter {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub(crate) struct Row {
     pub(crate) letters: [Letter; 5],
 }
 
 impl Row {
     pub(crate) fn set_colors(&mut self, word: &str) {
-        let mut unused_letters = [true; 5];
+        let guess = self.letters.iter().map(|l| l.char).collect::<String>();
+        for (letter, color) in self.letters.iter_mut().zip(score(&guess, word)) {
+            letter.color = Some(color);
+        }
+    }
+}
 
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..5 {
-            let char = self.letters[i].char.to_ascii_lowercase();
-            if word.chars().nth(i).map(|c| c.to_ascii_lowercase()) == Some(char) {
-                unused_letters[i] = false;
-                self.letters[i].color = Some(Color::Green)
-            }
+fn letters(word: &str) -> [char; 5] {
+    let mut letters = [' '; 5];
+    for (letter, char) in letters.iter_mut().zip(word.chars()) {
+        *letter = char.to_ascii_lowercase();
+    }
+    letters
+}
+
+/// Colors for each letter of `guess` against `answer`, ignoring case.
+///
+/// Greens are handed out first, then each remaining letter of the answer can make at most one
+/// guessed letter yellow, left to right.
+pub(crate) fn score(guess: &str, answer: &str) -> [Color; 5] {
+    let guess = letters(guess);
+    let answer = letters(answer);
+    let mut colors = [Color::Gray; 5];
+    let mut unused_letters = [true; 5];
+
+    for i in 0..5 {
+        if guess[i] == answer[i] {
+            colors[i] = Color::Green;
+            unused_letters[i] = false;
         }
+    }
 
-        for i in 0..5 {
-            let char = self.letters[i].char.to_ascii_lowercase();
-            if self.letters[i].color.is_none() {
-                self.letters[i].color = if let Some((i, _)) = word
-                    .chars()
-                    .map(|c| c.to_ascii_lowercase())
-                    .enumerate()
-                    .find(|(i, c)| unused_letters[*i] && *c == char)
-                {
-                    unused_letters[i] = false;
-                    Some(Color::Yellow)
-                } else {
-                    Some(Color::Gray)
-                };
-            }
+    for i in 0..5 {
+        if colors[i] == Color::Green {
+            continue;
+        }
+        if let Some(j) = (0..5).find(|&j| unused_letters[j] && answer[j] == guess[i]) {
+            unused_letters[j] = false;
+            colors[i] = Color::Yellow;
         }
     }
+
+    colors
 }
 
+/// A puzzle as the NYT serves it.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Puzzle {
+    #[serde(default)]
+    pub(crate) id: u32,
+    pub(crate) solution: String,
+    pub(crate) print_date: NaiveDate,
+    pub(crate) days_since_launch: Option<u32>,
+    pub(crate) editor: Option<String>,
+}
+
+impl Puzzle {
+    /// Checks this is the puzzle for `date` and that the NYT's numbering agrees with ours, since
+    /// games are saved by number.
+    pub(crate) fn into_info(self, date: NaiveDate) -> Result<GameInfo, FetchError> {
+        let number = self
+            .days_since_launch
+            .or_else(|| wordle_number(self.print_date));
+        let Some(number) =
+            number.filter(|&n| self.print_date == date && Some(n) == wordle_number(date))
+        else {
+            return Err(FetchError::Mismatch {
+                date,
+                print_date: self.print_date,
+                number,
+            });
+        };
+
+        Ok(GameInfo {
+            id: self.id,
+            number,
+            word: Answer::new(&self.solution),
+            date_string: self.print_date.format("%Y-%m-%d").to_string(),
+            editor: self.editor,
+        })
+    }
+}
+
+/// Keys the obfuscation of answers. This keeps them from being read at a glance. It won't stop
+/// anyone determined.
+const ANSWER_KEY: &[u8] = b"no peeking!";
+
+/// A puzzle's answer, kept obfuscated in memory and at rest so it doesn't show up in save files,
+/// the puzzle database or debug output. Use [`Game::answer`] to get it once the game is over.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) struct Answer(Vec<u8>);
+
+impl Answer {
+    pub(crate) fn new(word: &str) -> Self {
+        Self(Answer::xor(word.as_bytes()))
+    }
+
+    fn xor(bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
+            .zip(ANSWER_KEY.iter().cycle())
+            .map(|(byte, key)| byte ^ key)
+            .collect()
+    }
+
+    /// The answer in plain text. Only for scoring and for games that are over.
+    pub(crate) fn reveal(&self) -> String {
+        String::from_utf8_lossy(&Answer::xor(&self.0)).into_owned()
+    }
+}
+
+impl fmt::Debug for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Answer(..)")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub(crate) struct GameInfo {
-    #[serde(default, rename = "days_since_launch")]
+    pub(crate) id: u32,
     pub(crate) number: u32,
-    #[serde(rename = "solution")]
-    pub(crate) word: String,
-    #[serde(rename = "print_date")]
+    pub(crate) word: Answer,
     pub(crate) date_string: String,
+    pub(crate) editor: Option<String>,
 }
 
 impl GameInfo {
-    pub(crate) async fn today() -> anyhow::Result<Self> {
-        GameInfo::at(Utc::now().date_naive()).await
+    /// Fetches the puzzle for `date` from `source`, the base URL puzzles are served under.
+    pub(crate) async fn at(source: &str, date: NaiveDate) -> Result<Self, FetchError> {
+        GameInfo::fetch(fetch::client(), source, date).await
+    }
+
+    pub(crate) async fn fetch(
+        client: &fetch::Client,
+        source: &str,
+        date: NaiveDate,
+    ) -> Result<Self, FetchError> {
+        let url = format!(
+            "{}/{}.json",
+            source.trim_end_matches('/'),
+            date.format("%Y-%m-%d")
+        );
+        client.get_json::<Puzzle>(&url).await?.into_info(date)
+    }
+
+    pub(crate) fn date(&self) -> Option<NaiveDate> {
+        self.date_string.parse().ok()
+    }
+
+    pub(crate) fn title(&self) -> String {
+        let title = format!("Wordle #{} - {}", self.number, self.date_string);
+        match &self.editor {
+            Some(editor) => format!("{title}, edited by {editor}"),
+            None => title,
+        }
+    }
+
+    pub(crate) fn seeded(seed: u64) -> Self {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let word = rng
+            .choice(answers().collect::<Vec<_>>())
+            .expect("answer list is empty");
+        Self {
+            id: 0,
+            number: 0,
+            word: Answer::new(word),
+            date_string: format!("seed {seed}"),
+            editor: None,
+        }
     }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SubmitError {
+    NotEnoughLetters,
+    NotInWordList,
+    MustBeAt(char, usize),
+    MustContain(char),
+}
 
-    pub(crate) async fn at(date: NaiveDate) -> anyhow::Result<Self> {
-        let date = date.format("%Y-%m-%d");
-        let url = format!("https://www.nytimes.com/svc/wordle/v2/{date}.json");
-        let res = reqwest::get(url).await?;
-        Ok(res.json::<Self>().await?)
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::NotEnoughLetters => write!(f, "Not enough letters"),
+            SubmitError::NotInWordList => write!(f, "Not in word list"),
+            SubmitError::MustBeAt(char, i) => {
+                let ordinal = ["1st", "2nd", "3rd", "4th", "5th"][*i];
+                write!(f, "{ordinal} letter must be {char}")
+            }
+            SubmitError::MustContain(char) => write!(f, "Guess must contain {char}"),
+        }
     }
 }
 
@@ -89,6 +251,11 @@ pub(crate) struct Game {
     pub(crate) grid: [Row; 6],
     pub(crate) index: (usize, usize),
     pub(crate) info: GameInfo,
+    /// When the first letter was typed.
+    pub(crate) started: Option<DateTime<Utc>>,
+    pub(crate) finished: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub(crate) hard_mode: bool,
 }
 
 impl Game {
@@ -100,6 +267,15 @@ impl Game {
                 .any(|row| row.letters.iter().all(|l| l.color.is_none()))
     }
 
+    pub(crate) fn has_started(&self) -> bool {
+        self.index != (0, 0)
+    }
+
+    /// How long the game took, for games timed from start to finish.
+    pub(crate) fn time(&self) -> Option<TimeDelta> {
+        Some(self.finished? - self.started?)
+    }
+
     pub(crate) fn won_in(&self) -> Option<usize> {
         self.grid
             .iter()
@@ -108,6 +284,28 @@ impl Game {
             .map(|(i, _)| i + 1)
     }
 
+    /// The answer, once the game is over.
+    pub(crate) fn answer(&self) -> Option<String> {
+        self.has_finished().then(|| self.info.word.reveal())
+    }
+
+    pub(crate) fn result(&self) -> Option<Cow<'_, str>> {
+        if !self.has_finished() {
+            return None;
+        }
+
+        Some(match self.won_in() {
+            Some(1) => "Genius".into(),
+            Some(2) => "Magnificent".into(),
+            Some(3) => "Impressive".into(),
+            Some(4) => "Splendid".into(),
+            Some(5) => "Great".into(),
+            Some(6) => "Phew".into(),
+            None => self.info.word.reveal().to_uppercase().into(),
+            _ => unreachable!(),
+        })
+    }
+
     pub(crate) fn add_char(&mut self, char: char) {
         if self.has_finished() {
             return;
@@ -116,6 +314,7 @@ impl Game {
         if self.index.1 >= 5 {
             return;
         }
+        self.started.get_or_insert_with(Utc::now);
         self.grid[self.index.0].letters[self.index.1].char = char;
         self.index.1 += 1;
     }
@@ -132,13 +331,13 @@ impl Game {
         self.grid[self.index.0].letters[self.index.1].char = ' ';
     }
 
-    pub(crate) fn submit(&mut self) {
+    pub(crate) fn submit(&mut self) -> Result<(), SubmitError> {
         if self.has_finished() {
-            return;
+            return Ok(());
         }
 
         if self.index.1 < 5 {
-            return;
+            return Err(SubmitError::NotEnoughLetters);
         }
 
         let word = self.grid[self.index.0]
@@ -148,11 +347,68 @@ impl Game {
             .collect::<String>()
             .to_lowercase();
 
-        if include_str!("./wordlist.txt").lines().any(|w| w == word) {
-            self.grid[self.index.0].set_colors(&self.info.word);
-            self.index.0 += 1;
-            self.index.1 = 0;
+        if !is_allowed(&word) {
+            return Err(SubmitError::NotInWordList);
+        }
+
+        if self.hard_mode {
+            self.check_hints()?;
+        }
+
+        self.grid[self.index.0].set_colors(&self.info.word.reveal());
+        self.index.0 += 1;
+        self.index.1 = 0;
+        if self.has_finished() {
+            self.finished = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn guess(&mut self, word: &str) -> Result<(), SubmitError> {
+        while self.index.1 > 0 {
+            self.backspace();
+        }
+        if word.chars().count() > 5 {
+            return Err(SubmitError::NotInWordList);
         }
+
+        for char in word.chars() {
+            self.add_char(char.to_ascii_uppercase());
+        }
+        let result = self.submit();
+        if result.is_err() {
+            while self.index.1 > 0 {
+                self.backspace();
+            }
+        }
+        result
+    }
+
+    fn check_hints(&self) -> Result<(), SubmitError> {
+        let guess = &self.grid[self.index.0];
+
+        for row in &self.grid[..self.index.0] {
+            for (i, letter) in row.letters.iter().enumerate() {
+                if letter.color == Some(Color::Green) && guess.letters[i].char != letter.char {
+                    return Err(SubmitError::MustBeAt(letter.char, i));
+                }
+            }
+
+            for letter in &row.letters {
+                let count = |row: &Row, min: Option<Color>| {
+                    row.letters
+                        .iter()
+                        .filter(|l| l.char == letter.char && l.color >= min)
+                        .count()
+                };
+
+                if count(guess, None) < count(row, Some(Color::Yellow)) {
+                    return Err(SubmitError::MustContain(letter.char));
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -162,6 +418,150 @@ impl From<GameInfo> for Game {
             grid: [Row::default(); 6],
             index: (0, 0),
             info,
+            started: None,
+            finished: None,
+            hard_mode: config().hard_mode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use Color::{Gray as X, Green as G, Yellow as Y};
+
+    /// Scores by counting instead of marking off letters.
+    fn reference(guess: &str, answer: &str) -> [Color; 5] {
+        let guess = guess.as_bytes();
+        let answer = answer.as_bytes();
+        let mut remaining = [0; 26];
+        let mut colors = [Color::Gray; 5];
+
+        for i in 0..5 {
+            if guess[i] == answer[i] {
+                colors[i] = Color::Green;
+            } else {
+                remaining[(answer[i] - b'a') as usize] += 1;
+            }
+        }
+        for i in 0..5 {
+            let count = &mut remaining[(guess[i] - b'a') as usize];
+            if colors[i] != Color::Green && *count > 0 {
+                *count -= 1;
+                colors[i] = Color::Yellow;
+            }
+        }
+
+        colors
+    }
+
+    #[test]
+    fn duplicate_letters() {
+        for (guess, answer, colors) in [
+            ("speed", "abide", [X, X, Y, X, Y]),
+            ("llama", "hello", [Y, Y, X, X, X]),
+            ("robot", "floor", [Y, Y, X, G, X]),
+            ("eerie", "theme", [Y, X, X, X, G]),
+            ("allow", "loyal", [Y, Y, Y, Y, X]),
+            ("sassy", "class", [Y, Y, X, G, X]),
+            ("geese", "elide", [X, Y, X, X, G]),
+            ("abbey", "babes", [Y, Y, G, G, X]),
+        ] {
+            assert_eq!(score(guess, answer), colors, "{guess} against {answer}");
+        }
+    }
+
+    #[test]
+    fn ignores_case() {
+        assert_eq!(score("SPEED", "abide"), score("speed", "ABIDE"));
+    }
+
+    #[test]
+    fn set_colors_uses_score() {
+        let mut row = Row::default();
+        for (letter, char) in row.letters.iter_mut().zip("LLAMA".chars()) {
+            letter.char = char;
+        }
+        row.set_colors("hello");
+        assert_eq!(row.letters.map(|l| l.color), [Y, Y, X, X, X].map(Some));
+    }
+
+    #[test]
+    fn hides_answer_until_finished() {
+        let answer = Answer::new("crane");
+        assert_ne!(answer.0, b"crane");
+        assert_eq!(format!("{answer:?}"), "Answer(..)");
+        assert!(!postcard::to_allocvec(&answer).unwrap().ends_with(b"crane"));
+
+        let mut game = Game::from(GameInfo {
+            id: 0,
+            number: 0,
+            word: answer,
+            date_string: "2021-06-19".to_string(),
+            editor: None,
+        });
+        assert!(!format!("{game:?}").contains("crane"));
+        game.guess("trace").unwrap();
+        assert_eq!(game.answer(), None);
+        game.guess("crane").unwrap();
+        assert_eq!(game.answer().as_deref(), Some("crane"));
+    }
+
+    #[test]
+    fn matches_reference_for_every_answer_pair() {
+        let answers = answers().collect::<Vec<_>>();
+        for guess in &answers {
+            for answer in &answers {
+                assert_eq!(score(guess, answer), reference(guess, answer));
+            }
+        }
+    }
+
+    /// Few distinct letters, so most words have duplicates.
+    fn word() -> impl Strategy<Value = String> {
+        "[abcde]{5}"
+    }
+
+    proptest! {
+        #[test]
+        fn answer_scores_all_green(answer in word()) {
+            prop_assert_eq!(score(&answer, &answer), [G; 5]);
+        }
+
+        #[test]
+        fn hints_never_exceed_answer_count(guess in word(), answer in word()) {
+            let colors = score(&guess, &answer);
+            for char in guess.chars() {
+                let hints = guess
+                    .chars()
+                    .zip(colors)
+                    .filter(|&(c, color)| c == char && color != Color::Gray)
+                    .count();
+                prop_assert!(hints <= answer.chars().filter(|&c| c == char).count());
+            }
+        }
+
+        #[test]
+        fn green_exactly_where_letters_match(guess in word(), answer in word()) {
+            let colors = score(&guess, &answer);
+            for ((g, a), color) in guess.chars().zip(answer.chars()).zip(colors) {
+                prop_assert_eq!(g == a, color == Color::Green);
+            }
+        }
+
+        #[test]
+        fn matches_reference(guess in word(), answer in word()) {
+            prop_assert_eq!(score(&guess, &answer), reference(&guess, &answer));
+        }
+
+        #[test]
+        fn matches_reference_for_real_words(
+            guess in prop::sample::select(allowed().collect::<Vec<_>>()),
+            answer in prop::sample::select(answers().collect::<Vec<_>>()),
+        ) {
+            prop_assert_eq!(score(guess, answer), reference(guess, answer));
         }
     }
 }